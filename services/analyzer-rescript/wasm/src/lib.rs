@@ -2,6 +2,7 @@
 // AOT compilation to WebAssembly for maximum performance
 
 use wasm_bindgen::prelude::*;
+use serde::Serialize;
 use std::collections::HashMap;
 
 #[wasm_bindgen]
@@ -92,6 +93,153 @@ pub fn detect_changes(old_text: &str, new_text: &str) -> f64 {
     distance as f64 / max_len
 }
 
+/// Similarity threshold above which a removed block and an added block in
+/// the same position are reported as one "modified" block rather than a
+/// separate addition and removal.
+const MODIFIED_SIMILARITY_THRESHOLD: f64 = 0.6;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModifiedBlock {
+    pub old: String,
+    pub new: String,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct BlockDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<ModifiedBlock>,
+}
+
+/// Structured block-level diff between two policy snapshots: which
+/// paragraphs/sentences were added, removed, or modified, instead of a
+/// single document-wide Levenshtein ratio. Blocks are aligned with an LCS
+/// over the tokenized blocks; unmatched runs are then paired position-by-
+/// position and classified as "modified" when their Levenshtein similarity
+/// clears [`MODIFIED_SIMILARITY_THRESHOLD`], or as a plain add+remove
+/// otherwise.
+#[wasm_bindgen]
+pub fn diff_snapshots(old_text: &str, new_text: &str) -> JsValue {
+    serde_wasm_bindgen::to_value(&diff_blocks(old_text, new_text)).unwrap()
+}
+
+/// Native-Rust entry point for [`diff_snapshots`]'s block diff, for
+/// in-process Rust callers (e.g. the collector service) that want the
+/// structured `BlockDiff` directly instead of going through `JsValue`.
+pub fn diff_blocks(old_text: &str, new_text: &str) -> BlockDiff {
+    let old_blocks = tokenize_blocks(old_text);
+    let new_blocks = tokenize_blocks(new_text);
+    compute_block_diff(&old_blocks, &new_blocks)
+}
+
+/// Splits a document into paragraphs on blank lines, then into sentences
+/// within each paragraph, discarding empty blocks.
+fn tokenize_blocks(text: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+
+    for paragraph in text.split("\n\n") {
+        for sentence in paragraph.split_inclusive(['.', '!', '?']) {
+            let trimmed = sentence.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !trimmed.is_empty() {
+                blocks.push(trimmed);
+            }
+        }
+    }
+
+    blocks
+}
+
+/// Indices `(i, j)` of blocks common to both sequences, in order, found via
+/// a standard longest-common-subsequence alignment.
+fn lcs_matches(old: &[String], new: &[String]) -> Vec<(usize, usize)> {
+    let n = old.len();
+    let m = new.len();
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if old[i] == new[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    matches
+}
+
+fn compute_block_diff(old_blocks: &[String], new_blocks: &[String]) -> BlockDiff {
+    let matches = lcs_matches(old_blocks, new_blocks);
+    let mut diff = BlockDiff::default();
+
+    let mut old_cursor = 0;
+    let mut new_cursor = 0;
+
+    let mut boundaries: Vec<(usize, usize)> = matches;
+    boundaries.push((old_blocks.len(), new_blocks.len()));
+
+    for (match_i, match_j) in boundaries {
+        classify_run(
+            &old_blocks[old_cursor..match_i],
+            &new_blocks[new_cursor..match_j],
+            &mut diff,
+        );
+        old_cursor = (match_i + 1).min(old_blocks.len());
+        new_cursor = (match_j + 1).min(new_blocks.len());
+    }
+
+    diff
+}
+
+/// Classifies an unmatched run of blocks (bounded by LCS matches on either
+/// side) position-by-position: a paired old/new block is "modified" when
+/// similar enough, otherwise both are reported as a removal and an
+/// addition; any leftover blocks on the longer side are pure adds/removes.
+fn classify_run(old_run: &[String], new_run: &[String], diff: &mut BlockDiff) {
+    let paired = old_run.len().min(new_run.len());
+
+    for k in 0..paired {
+        let similarity = block_similarity(&old_run[k], &new_run[k]);
+        if similarity >= MODIFIED_SIMILARITY_THRESHOLD {
+            diff.modified.push(ModifiedBlock {
+                old: old_run[k].clone(),
+                new: new_run[k].clone(),
+                similarity,
+            });
+        } else {
+            diff.removed.push(old_run[k].clone());
+            diff.added.push(new_run[k].clone());
+        }
+    }
+
+    diff.removed.extend(old_run[paired..].iter().cloned());
+    diff.added.extend(new_run[paired..].iter().cloned());
+}
+
+fn block_similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.len().max(b.len()) as f64;
+    if max_len == 0.0 {
+        return 1.0;
+    }
+    1.0 - (levenshtein_distance(a, b) as f64 / max_len)
+}
+
 /// Optimized Levenshtein distance for WASM
 fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     let len1 = s1.len();
@@ -145,4 +293,27 @@ mod tests {
         assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
         assert_eq!(levenshtein_distance("hello", "hello"), 0);
     }
+
+    #[test]
+    fn test_block_diff_classifies_added_removed_and_modified() {
+        let old = "We protect your privacy.\n\nAccounts may be suspended for abuse.";
+        let new = "We protect your privacy carefully.\n\nWe never sell your data.";
+
+        let diff = compute_block_diff(&tokenize_blocks(old), &tokenize_blocks(new));
+
+        assert_eq!(diff.modified.len(), 1);
+        assert!(diff.modified[0].similarity >= MODIFIED_SIMILARITY_THRESHOLD);
+        assert!(diff.removed.iter().any(|b| b.contains("suspended")));
+        assert!(diff.added.iter().any(|b| b.contains("never sell")));
+    }
+
+    #[test]
+    fn test_block_diff_identical_text_has_no_changes() {
+        let text = "Nothing here has changed at all.";
+        let diff = compute_block_diff(&tokenize_blocks(text), &tokenize_blocks(text));
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(diff.modified.is_empty());
+    }
 }
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use serde::Deserialize;
 use std::env;
+use std::fmt;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -9,6 +10,8 @@ pub struct Config {
     pub redis: RedisConfig,
     pub collector: CollectorConfig,
     pub platforms: PlatformCredentials,
+    pub storage: StorageConfig,
+    pub maintenance: MaintenanceConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -32,6 +35,20 @@ pub struct CollectorConfig {
     pub max_concurrent_collections: usize,
     pub default_check_frequency: u64,
     pub user_agent: String,
+    pub job_queue: JobQueueConfig,
+    /// How long an ETag/Last-Modified/checksum entry stays valid in the
+    /// Redis-backed fetch cache (see `crate::fetch_cache`) before a
+    /// collection cycle re-fetches the document unconditionally.
+    pub fetch_cache_ttl_secs: u64,
+}
+
+/// Tunables for the durable `job_queue` worker in `crate::scheduler`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobQueueConfig {
+    pub max_retries: i32,
+    pub heartbeat_interval_secs: u64,
+    pub stale_after_secs: i64,
+    pub slow_job_threshold_secs: u64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -41,6 +58,7 @@ pub struct PlatformCredentials {
     pub linkedin: Option<LinkedInCredentials>,
     pub youtube: Option<YouTubeCredentials>,
     pub bluesky: Option<BlueskyCredentials>,
+    pub fediverse: Option<FediverseCredentials>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -74,35 +92,249 @@ pub struct BlueskyCredentials {
     pub app_password: String,
 }
 
+/// The RSA keypair used to sign HTTP requests to Fediverse instances that
+/// require HTTP Signatures on authenticated fetches (see `crate::signing`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct FediverseCredentials {
+    pub key_id: String,
+    pub private_key_pem: String,
+}
+
+/// Selects and configures the [`crate::storage::SnapshotStorage`] backend
+/// that full policy snapshot text is written through.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageConfig {
+    /// `"local"` or `"s3"`.
+    pub backend: String,
+    pub local_path: String,
+    pub s3: Option<S3StorageConfig>,
+}
+
+/// Tunables for `crate::maintenance`'s retention/vacuum/verification runs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceConfig {
+    pub retention_days: i64,
+    /// Whether a low-frequency scheduled maintenance job runs alongside
+    /// the on-demand `/api/maintenance/*` endpoints.
+    pub scheduled_enabled: bool,
+    pub schedule_cron: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3StorageConfig {
+    pub bucket: String,
+    pub region: String,
+    pub endpoint: Option<String>,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+/// Every malformed or missing environment variable found while building a
+/// `Config`, collected so operators see the full list of problems in one
+/// error instead of fixing them one `parse()?` at a time.
+#[derive(Debug, Default)]
+pub struct ConfigError {
+    problems: Vec<String>,
+}
+
+impl ConfigError {
+    fn push(&mut self, key: &'static str, reason: impl fmt::Display, allowed: Option<&[&str]>) {
+        let mut problem = format!("{} ({})", key, reason);
+        if let Some(allowed) = allowed {
+            problem.push_str(&format!(" - allowed values: {}", allowed.join(", ")));
+        }
+        self.problems.push(problem);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "invalid configuration, {} problem(s):", self.problems.len())?;
+        for problem in &self.problems {
+            writeln!(f, "  - {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Reads a single environment variable into a typed value, pushing a
+/// description onto `$errors` instead of bailing out on the first bad
+/// `parse()` the way a plain `env::var(..)?.parse()?` chain would.
+///
+/// Forms:
+/// - `from_env_var!(errors, "KEY", default = "x")` - string with a default
+/// - `from_env_var!(errors, "KEY", default = "1", parse = u16)` - typed with a default
+/// - `from_env_var!(errors, "KEY", required)` - required string
+/// - `from_env_var!(errors, "KEY", required, allowed = ["a", "b"])` - required, constrained
+macro_rules! from_env_var {
+    ($errors:expr, $key:literal, default = $default:expr) => {{
+        env::var($key).unwrap_or_else(|_| $default.to_string())
+    }};
+    ($errors:expr, $key:literal, default = $default:expr, parse = $ty:ty) => {{
+        let raw = env::var($key).unwrap_or_else(|_| $default.to_string());
+        match raw.parse::<$ty>() {
+            Ok(value) => value,
+            Err(e) => {
+                $errors.push($key, e, None);
+                Default::default()
+            }
+        }
+    }};
+    ($errors:expr, $key:literal, required) => {{
+        match env::var($key) {
+            Ok(value) => value,
+            Err(_) => {
+                $errors.push($key, "missing required variable", None);
+                String::new()
+            }
+        }
+    }};
+    ($errors:expr, $key:literal, required, allowed = [$($allowed:literal),+ $(,)?]) => {{
+        match env::var($key) {
+            Ok(value) if [$($allowed),+].contains(&value.as_str()) => value,
+            Ok(value) => {
+                $errors.push(
+                    $key,
+                    format!("'{}' is not a recognized value", value),
+                    Some(&[$($allowed),+]),
+                );
+                String::new()
+            }
+            Err(_) => {
+                $errors.push($key, "missing required variable", Some(&[$($allowed),+]));
+                String::new()
+            }
+        }
+    }};
+}
+
 impl Config {
     pub fn from_env() -> Result<Self> {
-        dotenv::dotenv().ok();
+        Self::load_profile_dotenv();
 
-        let config = Self {
-            server: ServerConfig {
-                port: env::var("COLLECTOR_PORT")
-                    .unwrap_or_else(|_| "3001".to_string())
-                    .parse()?,
-            },
+        let mut errors = ConfigError::default();
+
+        let port = from_env_var!(errors, "COLLECTOR_PORT", default = "3001", parse = u16);
+        let database_url = from_env_var!(errors, "DATABASE_URL", required);
+        let database_max_connections = from_env_var!(
+            errors,
+            "DATABASE_MAX_CONNECTIONS",
+            default = "20",
+            parse = u32
+        );
+        let redis_url = from_env_var!(errors, "REDIS_URL", required);
+        let max_concurrent_collections = from_env_var!(
+            errors,
+            "MAX_CONCURRENT_COLLECTIONS",
+            default = "10",
+            parse = usize
+        );
+        let default_check_frequency = from_env_var!(
+            errors,
+            "DEFAULT_CHECK_FREQUENCY",
+            default = "60",
+            parse = u64
+        );
+        let user_agent = from_env_var!(
+            errors,
+            "USER_AGENT",
+            default = "NUJ Social Media Monitor/1.0 (https://nuj.org.uk; monitor@nuj.org.uk)"
+        );
+        let job_max_retries =
+            from_env_var!(errors, "JOB_QUEUE_MAX_RETRIES", default = "5", parse = i32);
+        let job_heartbeat_interval_secs = from_env_var!(
+            errors,
+            "JOB_QUEUE_HEARTBEAT_INTERVAL_SECS",
+            default = "30",
+            parse = u64
+        );
+        let job_stale_after_secs = from_env_var!(
+            errors,
+            "JOB_QUEUE_STALE_AFTER_SECS",
+            default = "180",
+            parse = i64
+        );
+        let job_slow_threshold_secs = from_env_var!(
+            errors,
+            "JOB_QUEUE_SLOW_THRESHOLD_SECS",
+            default = "120",
+            parse = u64
+        );
+        let fetch_cache_ttl_secs = from_env_var!(
+            errors,
+            "FETCH_CACHE_TTL_SECS",
+            default = "86400",
+            parse = u64
+        );
+
+        let maintenance_retention_days = from_env_var!(
+            errors,
+            "MAINTENANCE_RETENTION_DAYS",
+            default = "180",
+            parse = i64
+        );
+        let maintenance_scheduled_enabled = from_env_var!(
+            errors,
+            "MAINTENANCE_SCHEDULE_ENABLED",
+            default = "false",
+            parse = bool
+        );
+        let maintenance_schedule_cron = from_env_var!(
+            errors,
+            "MAINTENANCE_SCHEDULE_CRON",
+            default = "0 0 3 * * Sun"
+        );
+
+        let storage_backend = from_env_var!(errors, "STORAGE_BACKEND", default = "local");
+        if !["local", "s3"].contains(&storage_backend.as_str()) {
+            errors.push(
+                "STORAGE_BACKEND",
+                format!("'{}' is not a recognized value", storage_backend),
+                Some(&["local", "s3"]),
+            );
+        }
+        let storage_local_path =
+            from_env_var!(errors, "STORAGE_LOCAL_PATH", default = "./data/snapshots");
+        let storage_s3 = if storage_backend == "s3" {
+            Some(S3StorageConfig {
+                bucket: from_env_var!(errors, "STORAGE_S3_BUCKET", required),
+                region: from_env_var!(errors, "STORAGE_S3_REGION", default = "us-east-1"),
+                endpoint: env::var("STORAGE_S3_ENDPOINT").ok(),
+                access_key: from_env_var!(errors, "STORAGE_S3_ACCESS_KEY", required),
+                secret_key: from_env_var!(errors, "STORAGE_S3_SECRET_KEY", required),
+            })
+        } else {
+            None
+        };
+
+        if !errors.is_empty() {
+            return Err(errors.into());
+        }
+
+        Ok(Self {
+            server: ServerConfig { port },
             database: DatabaseConfig {
-                url: env::var("DATABASE_URL")?,
-                max_connections: env::var("DATABASE_MAX_CONNECTIONS")
-                    .unwrap_or_else(|_| "20".to_string())
-                    .parse()?,
-            },
-            redis: RedisConfig {
-                url: env::var("REDIS_URL")?,
+                url: database_url,
+                max_connections: database_max_connections,
             },
+            redis: RedisConfig { url: redis_url },
             collector: CollectorConfig {
-                max_concurrent_collections: env::var("MAX_CONCURRENT_COLLECTIONS")
-                    .unwrap_or_else(|_| "10".to_string())
-                    .parse()?,
-                default_check_frequency: env::var("DEFAULT_CHECK_FREQUENCY")
-                    .unwrap_or_else(|_| "60".to_string())
-                    .parse()?,
-                user_agent: env::var("USER_AGENT").unwrap_or_else(|_| {
-                    "NUJ Social Media Monitor/1.0 (https://nuj.org.uk; monitor@nuj.org.uk)".to_string()
-                }),
+                max_concurrent_collections,
+                default_check_frequency,
+                user_agent,
+                job_queue: JobQueueConfig {
+                    max_retries: job_max_retries,
+                    heartbeat_interval_secs: job_heartbeat_interval_secs,
+                    stale_after_secs: job_stale_after_secs,
+                    slow_job_threshold_secs: job_slow_threshold_secs,
+                },
+                fetch_cache_ttl_secs,
             },
             platforms: PlatformCredentials {
                 twitter: Self::get_twitter_creds(),
@@ -110,10 +342,39 @@ impl Config {
                 linkedin: Self::get_linkedin_creds(),
                 youtube: Self::get_youtube_creds(),
                 bluesky: Self::get_bluesky_creds(),
+                fediverse: Self::get_fediverse_creds(),
+            },
+            storage: StorageConfig {
+                backend: storage_backend,
+                local_path: storage_local_path,
+                s3: storage_s3,
             },
+            maintenance: MaintenanceConfig {
+                retention_days: maintenance_retention_days,
+                scheduled_enabled: maintenance_scheduled_enabled,
+                schedule_cron: maintenance_schedule_cron,
+            },
+        })
+    }
+
+    /// Loads a profile-specific dotenv file before falling back to the plain
+    /// `.env`, mirroring flodgatt's `merge_dotenv`: set `ENV=production` or
+    /// `ENV=development` to pick `.env.production` / `.env.development` so
+    /// operators can keep distinct dev/prod credential sets side by side.
+    fn load_profile_dotenv() {
+        let profile_file = match env::var("ENV").ok().as_deref() {
+            Some("production") => Some(".env.production"),
+            Some("development") => Some(".env.development"),
+            _ => None,
         };
 
-        Ok(config)
+        if let Some(path) = profile_file {
+            if dotenv::from_filename(path).is_ok() {
+                return;
+            }
+        }
+
+        dotenv::dotenv().ok();
     }
 
     fn get_twitter_creds() -> Option<TwitterCredentials> {
@@ -161,4 +422,16 @@ impl Config {
             _ => None,
         }
     }
+
+    fn get_fediverse_creds() -> Option<FediverseCredentials> {
+        match (
+            env::var("FEDIVERSE_SIGNING_KEY_ID").ok(),
+            env::var("FEDIVERSE_SIGNING_PRIVATE_KEY_PEM").ok(),
+        ) {
+            (Some(key_id), Some(private_key_pem)) => {
+                Some(FediverseCredentials { key_id, private_key_pem })
+            }
+            _ => None,
+        }
+    }
 }
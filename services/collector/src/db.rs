@@ -1,237 +1,750 @@
 use anyhow::Result;
-use chrono::Utc;
-use sqlx::PgPool;
+use async_trait::async_trait;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
-use crate::models::{Platform, PolicyDocument, PolicySnapshot, PolicyChange};
+use crate::models::{
+    ChangeAnalytics, ChangeAnalyticsFilter, ChangeAnalyticsSummary, MaintenanceRun, Platform,
+    PlatformChangeCount, PolicyChange, PolicyDocument, PolicySnapshot, SeverityChangeCount,
+    WeeklyChangeCount,
+};
+use crate::queue::QueuedJob;
+use crate::storage::StorageRef;
 
-pub async fn get_active_platforms(pool: &PgPool) -> Result<Vec<Platform>> {
-    let platforms = sqlx::query_as::<_, Platform>(
-        r#"
-        SELECT * FROM platforms
-        WHERE monitoring_active = true
-        ORDER BY name
-        "#,
-    )
-    .fetch_all(pool)
-    .await?;
+/// Backend-agnostic data access for the collector. `handlers`, `platforms`,
+/// and `scheduler` all depend on this trait (via `Arc<dyn Repository>` in
+/// `AppState`) rather than a concrete `sqlx::PgPool`, so an operator can run
+/// the collector against SQLite for local testing or embedded deployments
+/// by adding a second implementor without touching collection logic.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn get_active_platforms(&self) -> Result<Vec<Platform>>;
 
-    Ok(platforms)
-}
+    async fn get_platform_by_id(&self, id: Uuid) -> Result<Option<Platform>>;
 
-pub async fn get_platform_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Platform>> {
-    let platform = sqlx::query_as::<_, Platform>(
-        r#"
-        SELECT * FROM platforms
-        WHERE id = $1
-        "#,
-    )
-    .bind(id)
-    .fetch_optional(pool)
-    .await?;
-
-    Ok(platform)
-}
+    async fn get_policy_documents_for_platform(&self, platform_id: Uuid) -> Result<Vec<PolicyDocument>>;
 
-pub async fn get_policy_documents_for_platform(
-    pool: &PgPool,
-    platform_id: Uuid,
-) -> Result<Vec<PolicyDocument>> {
-    let documents = sqlx::query_as::<_, PolicyDocument>(
-        r#"
-        SELECT * FROM policy_documents
-        WHERE platform_id = $1 AND is_current = true
-        ORDER BY document_type
-        "#,
-    )
-    .bind(platform_id)
-    .fetch_all(pool)
-    .await?;
-
-    Ok(documents)
-}
+    async fn create_or_update_policy_document(
+        &self,
+        platform_id: Uuid,
+        document_type: &str,
+        url: &str,
+        title: Option<&str>,
+    ) -> Result<PolicyDocument>;
 
-pub async fn create_or_update_policy_document(
-    pool: &PgPool,
-    platform_id: Uuid,
-    document_type: &str,
-    url: &str,
-    title: Option<&str>,
-) -> Result<PolicyDocument> {
-    let document = sqlx::query_as::<_, PolicyDocument>(
-        r#"
-        INSERT INTO policy_documents (platform_id, document_type, url, title, is_current)
-        VALUES ($1, $2, $3, $4, true)
-        ON CONFLICT (platform_id, url) DO UPDATE
-        SET last_seen_at = NOW(), is_current = true, title = COALESCE($4, policy_documents.title)
-        RETURNING *
-        "#,
-    )
-    .bind(platform_id)
-    .bind(document_type)
-    .bind(url)
-    .bind(title)
-    .fetch_one(pool)
-    .await?;
-
-    Ok(document)
-}
+    #[allow(clippy::too_many_arguments)]
+    async fn create_policy_snapshot(
+        &self,
+        policy_document_id: Uuid,
+        storage_ref: &StorageRef,
+        word_count: i32,
+        char_count: i32,
+        checksum: &str,
+        capture_method: &str,
+        previous_snapshot_id: Option<Uuid>,
+        diff_summary: Option<serde_json::Value>,
+    ) -> Result<PolicySnapshot>;
 
-pub async fn create_policy_snapshot(
-    pool: &PgPool,
-    policy_document_id: Uuid,
-    content_text: &str,
-    content_html: Option<&str>,
-    checksum: &str,
-    capture_method: &str,
-    previous_snapshot_id: Option<Uuid>,
-) -> Result<PolicySnapshot> {
-    let word_count = PolicySnapshot::calculate_word_count(content_text);
-    let char_count = PolicySnapshot::calculate_char_count(content_text);
-
-    let snapshot = sqlx::query_as::<_, PolicySnapshot>(
-        r#"
-        INSERT INTO policy_snapshots (
-            policy_document_id,
-            content_text,
-            content_html,
-            word_count,
-            char_count,
-            checksum,
-            capture_method,
-            previous_snapshot_id,
-            metadata
-        )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, '{}'::jsonb)
-        RETURNING *
-        "#,
-    )
-    .bind(policy_document_id)
-    .bind(content_text)
-    .bind(content_html)
-    .bind(word_count)
-    .bind(char_count)
-    .bind(checksum)
-    .bind(capture_method)
-    .bind(previous_snapshot_id)
-    .fetch_one(pool)
-    .await?;
-
-    Ok(snapshot)
-}
+    async fn get_latest_snapshot(&self, policy_document_id: Uuid) -> Result<Option<PolicySnapshot>>;
 
-pub async fn get_latest_snapshot(
-    pool: &PgPool,
-    policy_document_id: Uuid,
-) -> Result<Option<PolicySnapshot>> {
-    let snapshot = sqlx::query_as::<_, PolicySnapshot>(
-        r#"
-        SELECT * FROM policy_snapshots
-        WHERE policy_document_id = $1
-        ORDER BY captured_at DESC
-        LIMIT 1
-        "#,
-    )
-    .bind(policy_document_id)
-    .fetch_optional(pool)
-    .await?;
-
-    Ok(snapshot)
-}
+    #[allow(clippy::too_many_arguments)]
+    async fn create_policy_change(
+        &self,
+        policy_document_id: Uuid,
+        previous_snapshot_id: Option<Uuid>,
+        current_snapshot_id: Option<Uuid>,
+        change_type: &str,
+        change_summary: Option<&str>,
+        severity: &str,
+        confidence_score: f64,
+        affected_sections: serde_json::Value,
+        requires_member_notification: bool,
+    ) -> Result<PolicyChange>;
+
+    async fn get_recent_changes(&self, limit: i64) -> Result<Vec<PolicyChange>>;
+
+    async fn get_change_by_id(&self, id: Uuid) -> Result<Option<PolicyChange>>;
+
+    /// Grouped counts and summary statistics over `policy_changes` matching
+    /// `filter`, for the `/api/changes/analytics` dashboard endpoint.
+    async fn get_change_analytics(&self, filter: &ChangeAnalyticsFilter) -> Result<ChangeAnalytics>;
+
+    async fn update_platform_last_checked(&self, platform_id: Uuid) -> Result<()>;
+
+    /// Persists a new job onto `queue`, returning it in `new` status.
+    async fn enqueue_job(&self, queue: &str, job: serde_json::Value) -> Result<QueuedJob>;
+
+    /// Atomically claims the oldest eligible `new` job on `queue` (skipping
+    /// rows locked by other workers) and marks it `running`, or `None` if
+    /// there's nothing to do right now.
+    async fn claim_job(&self, queue: &str) -> Result<Option<QueuedJob>>;
+
+    /// Refreshes a running job's heartbeat so the reaper doesn't consider it
+    /// abandoned.
+    async fn heartbeat_job(&self, job_id: Uuid) -> Result<()>;
+
+    /// Removes a successfully finished job from the queue.
+    async fn complete_job(&self, job_id: Uuid) -> Result<()>;
 
-pub async fn create_policy_change(
-    pool: &PgPool,
-    policy_document_id: Uuid,
-    previous_snapshot_id: Option<Uuid>,
-    current_snapshot_id: Option<Uuid>,
-    change_type: &str,
-    change_summary: Option<&str>,
-) -> Result<PolicyChange> {
-    let change = sqlx::query_as::<_, PolicyChange>(
-        r#"
-        INSERT INTO policy_changes (
-            policy_document_id,
-            previous_snapshot_id,
-            current_snapshot_id,
-            change_type,
-            severity,
-            confidence_score,
-            change_summary,
-            requires_member_notification,
-            false_positive,
-            affected_sections,
-            metadata
-        )
-        VALUES ($1, $2, $3, $4, 'unknown', 0.00, $5, false, false, '[]'::jsonb, '{}'::jsonb)
-        RETURNING *
-        "#,
-    )
-    .bind(policy_document_id)
-    .bind(previous_snapshot_id)
-    .bind(current_snapshot_id)
-    .bind(change_type)
-    .bind(change_summary)
-    .fetch_one(pool)
-    .await?;
-
-    Ok(change)
+    /// Re-enqueues a failed job with an incremented retry count after an
+    /// exponential `base_backoff_secs * 2^retries` delay (capped at
+    /// `backoff_cap_secs`), or drops it once `max_retries` is exceeded.
+    async fn fail_job(
+        &self,
+        job_id: Uuid,
+        base_backoff_secs: i64,
+        backoff_cap_secs: i64,
+        max_retries: i32,
+    ) -> Result<()>;
+
+    /// Resets jobs stuck `running` with a heartbeat older than
+    /// `stale_after_secs` (their worker presumably died) back to `new`, so
+    /// another worker picks them up. Returns how many were reaped.
+    async fn reap_stale_jobs(&self, queue: &str, stale_after_secs: i64) -> Result<u64>;
+
+    /// Inserts a `running` row and returns it, to be finished with
+    /// [`Repository::complete_maintenance_run`] or
+    /// [`Repository::fail_maintenance_run`].
+    async fn start_maintenance_run(&self, operation: &str) -> Result<MaintenanceRun>;
+
+    async fn complete_maintenance_run(&self, id: Uuid, affected_rows: i64) -> Result<MaintenanceRun>;
+
+    async fn fail_maintenance_run(&self, id: Uuid, error: &str) -> Result<MaintenanceRun>;
+
+    async fn list_maintenance_runs(&self, limit: i64) -> Result<Vec<MaintenanceRun>>;
+
+    /// Sets `archived_at` on documents that haven't been seen in
+    /// `older_than_days`. Returns how many were archived.
+    async fn archive_stale_documents(&self, older_than_days: i64) -> Result<u64>;
+
+    /// Deletes snapshot rows older than `older_than_days`, always keeping
+    /// each document's most recent snapshot so `get_latest_snapshot` never
+    /// goes empty-handed. Returns how many were pruned.
+    async fn prune_old_snapshots(&self, older_than_days: i64) -> Result<u64>;
+
+    /// Runs `VACUUM ANALYZE` over the tables that grow with every
+    /// collection cycle.
+    async fn vacuum_analyze_tables(&self) -> Result<()>;
+
+    /// The most recent snapshot for every document, for the checksum
+    /// verification pass to recompute against.
+    async fn current_snapshots(&self) -> Result<Vec<PolicySnapshot>>;
+
+    /// Records that `snapshot_id`'s stored checksum no longer matches its
+    /// content by stamping `metadata.checksum_mismatch`.
+    async fn flag_checksum_mismatch(&self, snapshot_id: Uuid) -> Result<()>;
 }
 
-pub async fn get_recent_changes(
-    pool: &PgPool,
-    limit: i64,
-) -> Result<Vec<PolicyChange>> {
-    let changes = sqlx::query_as::<_, PolicyChange>(
-        r#"
-        SELECT * FROM policy_changes
-        WHERE detected_at > NOW() - INTERVAL '30 days'
-        ORDER BY detected_at DESC
-        LIMIT $1
-        "#,
-    )
-    .bind(limit)
-    .fetch_all(pool)
-    .await?;
-
-    Ok(changes)
+/// `Repository` implementation backed by PostgreSQL via `sqlx`.
+pub struct PostgresRepository {
+    pool: PgPool,
 }
 
-pub async fn get_change_by_id(
-    pool: &PgPool,
-    id: Uuid,
-) -> Result<Option<PolicyChange>> {
-    let change = sqlx::query_as::<_, PolicyChange>(
-        r#"
-        SELECT * FROM policy_changes
-        WHERE id = $1
-        "#,
-    )
-    .bind(id)
-    .fetch_optional(pool)
-    .await?;
-
-    Ok(change)
+impl PostgresRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
 }
 
-pub async fn update_platform_last_checked(
-    pool: &PgPool,
-    platform_id: Uuid,
-) -> Result<()> {
-    sqlx::query(
-        r#"
-        UPDATE platforms
-        SET updated_at = NOW(),
-            metadata = jsonb_set(
-                COALESCE(metadata, '{}'::jsonb),
-                '{last_checked_at}',
-                to_jsonb(NOW())
+#[async_trait]
+impl Repository for PostgresRepository {
+    async fn get_active_platforms(&self) -> Result<Vec<Platform>> {
+        let platforms = sqlx::query_as::<_, Platform>(
+            r#"
+            SELECT * FROM platforms
+            WHERE monitoring_active = true
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(platforms)
+    }
+
+    async fn get_platform_by_id(&self, id: Uuid) -> Result<Option<Platform>> {
+        let platform = sqlx::query_as::<_, Platform>(
+            r#"
+            SELECT * FROM platforms
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(platform)
+    }
+
+    async fn get_policy_documents_for_platform(&self, platform_id: Uuid) -> Result<Vec<PolicyDocument>> {
+        let documents = sqlx::query_as::<_, PolicyDocument>(
+            r#"
+            SELECT * FROM policy_documents
+            WHERE platform_id = $1 AND is_current = true
+            ORDER BY document_type
+            "#,
+        )
+        .bind(platform_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    async fn create_or_update_policy_document(
+        &self,
+        platform_id: Uuid,
+        document_type: &str,
+        url: &str,
+        title: Option<&str>,
+    ) -> Result<PolicyDocument> {
+        let document = sqlx::query_as::<_, PolicyDocument>(
+            r#"
+            INSERT INTO policy_documents (platform_id, document_type, url, title, is_current)
+            VALUES ($1, $2, $3, $4, true)
+            ON CONFLICT (platform_id, url) DO UPDATE
+            SET last_seen_at = NOW(), is_current = true, title = COALESCE($4, policy_documents.title)
+            RETURNING *
+            "#,
+        )
+        .bind(platform_id)
+        .bind(document_type)
+        .bind(url)
+        .bind(title)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    async fn create_policy_snapshot(
+        &self,
+        policy_document_id: Uuid,
+        storage_ref: &StorageRef,
+        word_count: i32,
+        char_count: i32,
+        checksum: &str,
+        capture_method: &str,
+        previous_snapshot_id: Option<Uuid>,
+        diff_summary: Option<serde_json::Value>,
+    ) -> Result<PolicySnapshot> {
+        let snapshot = sqlx::query_as::<_, PolicySnapshot>(
+            r#"
+            INSERT INTO policy_snapshots (
+                policy_document_id,
+                storage_backend,
+                storage_key,
+                word_count,
+                char_count,
+                checksum,
+                capture_method,
+                previous_snapshot_id,
+                diff_summary,
+                metadata
             )
-        WHERE id = $1
-        "#,
-    )
-    .bind(platform_id)
-    .execute(pool)
-    .await?;
-
-    Ok(())
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, '{}'::jsonb)
+            RETURNING *
+            "#,
+        )
+        .bind(policy_document_id)
+        .bind(storage_ref.backend)
+        .bind(&storage_ref.key)
+        .bind(word_count)
+        .bind(char_count)
+        .bind(checksum)
+        .bind(capture_method)
+        .bind(previous_snapshot_id)
+        .bind(diff_summary)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+
+    async fn get_latest_snapshot(&self, policy_document_id: Uuid) -> Result<Option<PolicySnapshot>> {
+        let snapshot = sqlx::query_as::<_, PolicySnapshot>(
+            r#"
+            SELECT * FROM policy_snapshots
+            WHERE policy_document_id = $1
+            ORDER BY captured_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(policy_document_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+
+    async fn create_policy_change(
+        &self,
+        policy_document_id: Uuid,
+        previous_snapshot_id: Option<Uuid>,
+        current_snapshot_id: Option<Uuid>,
+        change_type: &str,
+        change_summary: Option<&str>,
+        severity: &str,
+        confidence_score: f64,
+        affected_sections: serde_json::Value,
+        requires_member_notification: bool,
+    ) -> Result<PolicyChange> {
+        let confidence_score = Decimal::from_f64(confidence_score).unwrap_or_default();
+
+        let change = sqlx::query_as::<_, PolicyChange>(
+            r#"
+            INSERT INTO policy_changes (
+                policy_document_id,
+                previous_snapshot_id,
+                current_snapshot_id,
+                change_type,
+                severity,
+                confidence_score,
+                change_summary,
+                requires_member_notification,
+                false_positive,
+                affected_sections,
+                metadata
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, false, $9, '{}'::jsonb)
+            RETURNING *
+            "#,
+        )
+        .bind(policy_document_id)
+        .bind(previous_snapshot_id)
+        .bind(current_snapshot_id)
+        .bind(change_type)
+        .bind(severity)
+        .bind(confidence_score)
+        .bind(change_summary)
+        .bind(requires_member_notification)
+        .bind(affected_sections)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(change)
+    }
+
+    async fn get_recent_changes(&self, limit: i64) -> Result<Vec<PolicyChange>> {
+        let changes = sqlx::query_as::<_, PolicyChange>(
+            r#"
+            SELECT * FROM policy_changes
+            WHERE detected_at > NOW() - INTERVAL '30 days'
+            ORDER BY detected_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(changes)
+    }
+
+    async fn get_change_by_id(&self, id: Uuid) -> Result<Option<PolicyChange>> {
+        let change = sqlx::query_as::<_, PolicyChange>(
+            r#"
+            SELECT * FROM policy_changes
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(change)
+    }
+
+    async fn get_change_analytics(&self, filter: &ChangeAnalyticsFilter) -> Result<ChangeAnalytics> {
+        let mut by_platform_qb = QueryBuilder::new(
+            r#"
+            SELECT pf.id AS platform_id, pf.name AS platform_name, COUNT(*) AS count
+            FROM policy_changes pc
+            JOIN policy_documents pd ON pd.id = pc.policy_document_id
+            JOIN platforms pf ON pf.id = pd.platform_id
+            WHERE 1 = 1
+            "#,
+        );
+        push_change_filters(&mut by_platform_qb, filter);
+        by_platform_qb.push(" GROUP BY pf.id, pf.name ORDER BY count DESC");
+        let by_platform = by_platform_qb
+            .build_query_as::<PlatformChangeCount>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut by_severity_qb = QueryBuilder::new(
+            r#"
+            SELECT pc.severity AS severity, COUNT(*) AS count
+            FROM policy_changes pc
+            JOIN policy_documents pd ON pd.id = pc.policy_document_id
+            WHERE 1 = 1
+            "#,
+        );
+        push_change_filters(&mut by_severity_qb, filter);
+        by_severity_qb.push(" GROUP BY pc.severity ORDER BY count DESC");
+        let by_severity = by_severity_qb
+            .build_query_as::<SeverityChangeCount>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut by_week_qb = QueryBuilder::new(
+            r#"
+            SELECT date_trunc('week', pc.detected_at) AS week_start, COUNT(*) AS count
+            FROM policy_changes pc
+            JOIN policy_documents pd ON pd.id = pc.policy_document_id
+            WHERE 1 = 1
+            "#,
+        );
+        push_change_filters(&mut by_week_qb, filter);
+        by_week_qb.push(" GROUP BY week_start ORDER BY week_start");
+        let by_week = by_week_qb
+            .build_query_as::<WeeklyChangeCount>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut summary_qb = QueryBuilder::new(
+            r#"
+            SELECT
+                COUNT(*) AS total_changes,
+                EXTRACT(EPOCH FROM AVG(pc.reviewed_at - pc.detected_at) FILTER (WHERE pc.reviewed_at IS NOT NULL)) AS mean_time_to_review_secs,
+                COALESCE(AVG(CASE WHEN pc.requires_member_notification THEN 1.0 ELSE 0.0 END), 0.0) AS notification_rate
+            FROM policy_changes pc
+            JOIN policy_documents pd ON pd.id = pc.policy_document_id
+            WHERE 1 = 1
+            "#,
+        );
+        push_change_filters(&mut summary_qb, filter);
+        let summary = summary_qb
+            .build_query_as::<ChangeAnalyticsSummary>()
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(ChangeAnalytics {
+            by_platform,
+            by_severity,
+            by_week,
+            summary,
+        })
+    }
+
+    async fn update_platform_last_checked(&self, platform_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE platforms
+            SET updated_at = NOW(),
+                metadata = jsonb_set(
+                    COALESCE(metadata, '{}'::jsonb),
+                    '{last_checked_at}',
+                    to_jsonb(NOW())
+                )
+            WHERE id = $1
+            "#,
+        )
+        .bind(platform_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn enqueue_job(&self, queue: &str, job: serde_json::Value) -> Result<QueuedJob> {
+        let job = sqlx::query_as::<_, QueuedJob>(
+            r#"
+            INSERT INTO job_queue (queue, job, status, retries)
+            VALUES ($1, $2, 'new', 0)
+            RETURNING *
+            "#,
+        )
+        .bind(queue)
+        .bind(job)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn claim_job(&self, queue: &str) -> Result<Option<QueuedJob>> {
+        let job = sqlx::query_as::<_, QueuedJob>(
+            r#"
+            UPDATE job_queue
+            SET status = 'running', heartbeat = NOW(), updated_at = NOW()
+            WHERE id = (
+                SELECT id FROM job_queue
+                WHERE queue = $1
+                  AND status = 'new'
+                  AND (next_attempt_at IS NULL OR next_attempt_at <= NOW())
+                ORDER BY created_at
+                FOR UPDATE SKIP LOCKED
+                LIMIT 1
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(queue)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn heartbeat_job(&self, job_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE job_queue
+            SET heartbeat = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn complete_job(&self, job_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM job_queue WHERE id = $1")
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fail_job(
+        &self,
+        job_id: Uuid,
+        base_backoff_secs: i64,
+        backoff_cap_secs: i64,
+        max_retries: i32,
+    ) -> Result<()> {
+        // `retries` on the right-hand side of the SET list is the
+        // pre-increment count, so the first failure (retries = 0) backs off
+        // by `base_backoff_secs * 2^0`, the second by `* 2^1`, and so on,
+        // capped at `backoff_cap_secs`.
+        let retries: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE job_queue
+            SET status = 'new',
+                retries = retries + 1,
+                heartbeat = NULL,
+                updated_at = NOW(),
+                next_attempt_at = NOW() + make_interval(secs =>
+                    LEAST($3::double precision, $2::double precision * POWER(2, retries))
+                )
+            WHERE id = $1
+            RETURNING retries
+            "#,
+        )
+        .bind(job_id)
+        .bind(base_backoff_secs as f64)
+        .bind(backoff_cap_secs as f64)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if let Some((retries,)) = retries {
+            if retries > max_retries {
+                sqlx::query("DELETE FROM job_queue WHERE id = $1")
+                    .bind(job_id)
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn reap_stale_jobs(&self, queue: &str, stale_after_secs: i64) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE job_queue
+            SET status = 'new', heartbeat = NULL, updated_at = NOW()
+            WHERE queue = $1
+              AND status = 'running'
+              AND heartbeat < NOW() - make_interval(secs => $2)
+            "#,
+        )
+        .bind(queue)
+        .bind(stale_after_secs as f64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn start_maintenance_run(&self, operation: &str) -> Result<MaintenanceRun> {
+        let run = sqlx::query_as::<_, MaintenanceRun>(
+            r#"
+            INSERT INTO maintenance_runs (operation, status, started_at, affected_rows)
+            VALUES ($1, 'running', NOW(), 0)
+            RETURNING *
+            "#,
+        )
+        .bind(operation)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    async fn complete_maintenance_run(&self, id: Uuid, affected_rows: i64) -> Result<MaintenanceRun> {
+        let run = sqlx::query_as::<_, MaintenanceRun>(
+            r#"
+            UPDATE maintenance_runs
+            SET status = 'completed', completed_at = NOW(), affected_rows = $2
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(affected_rows)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    async fn fail_maintenance_run(&self, id: Uuid, error: &str) -> Result<MaintenanceRun> {
+        let run = sqlx::query_as::<_, MaintenanceRun>(
+            r#"
+            UPDATE maintenance_runs
+            SET status = 'failed', completed_at = NOW(), error = $2
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    async fn list_maintenance_runs(&self, limit: i64) -> Result<Vec<MaintenanceRun>> {
+        let runs = sqlx::query_as::<_, MaintenanceRun>(
+            r#"
+            SELECT * FROM maintenance_runs
+            ORDER BY started_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(runs)
+    }
+
+    async fn archive_stale_documents(&self, older_than_days: i64) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE policy_documents
+            SET archived_at = NOW()
+            WHERE archived_at IS NULL
+              AND last_seen_at < NOW() - make_interval(days => $1)
+            "#,
+        )
+        .bind(older_than_days as i32)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn prune_old_snapshots(&self, older_than_days: i64) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM policy_snapshots ps
+            WHERE ps.captured_at < NOW() - make_interval(days => $1)
+              AND ps.id != (
+                  SELECT id FROM policy_snapshots
+                  WHERE policy_document_id = ps.policy_document_id
+                  ORDER BY captured_at DESC
+                  LIMIT 1
+              )
+            "#,
+        )
+        .bind(older_than_days as i32)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn vacuum_analyze_tables(&self) -> Result<()> {
+        for table in ["policy_snapshots", "policy_changes", "policy_documents"] {
+            sqlx::query(&format!("VACUUM ANALYZE {table}"))
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn current_snapshots(&self) -> Result<Vec<PolicySnapshot>> {
+        let snapshots = sqlx::query_as::<_, PolicySnapshot>(
+            r#"
+            SELECT DISTINCT ON (policy_document_id) *
+            FROM policy_snapshots
+            ORDER BY policy_document_id, captured_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots)
+    }
+
+    async fn flag_checksum_mismatch(&self, snapshot_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE policy_snapshots
+            SET metadata = metadata || jsonb_build_object('checksum_mismatch', true, 'flagged_at', NOW())
+            WHERE id = $1
+            "#,
+        )
+        .bind(snapshot_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Appends `AND`-joined conditions for each set field of `filter` to `qb`,
+/// which must already have a `WHERE 1 = 1` (or equivalent) clause open.
+/// Shared by every `get_change_analytics` query so the grouped counts and
+/// the summary stats are always computed over the same filtered rows.
+fn push_change_filters(qb: &mut QueryBuilder<Postgres>, filter: &ChangeAnalyticsFilter) {
+    if let Some(platform_id) = filter.platform_id {
+        qb.push(" AND pd.platform_id = ").push_bind(platform_id);
+    }
+    if let Some(document_type) = &filter.document_type {
+        qb.push(" AND pd.document_type = ").push_bind(document_type.clone());
+    }
+    if let Some(change_type) = &filter.change_type {
+        qb.push(" AND pc.change_type = ").push_bind(change_type.clone());
+    }
+    if let Some(severity) = &filter.severity {
+        qb.push(" AND pc.severity = ").push_bind(severity.clone());
+    }
+    if let Some(from) = filter.from {
+        qb.push(" AND pc.detected_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        qb.push(" AND pc.detected_at <= ").push_bind(to);
+    }
+    if let Some(false_positive) = filter.false_positive {
+        qb.push(" AND pc.false_positive = ").push_bind(false_positive);
+    }
+    if let Some(reviewed) = filter.reviewed {
+        if reviewed {
+            qb.push(" AND pc.reviewed_at IS NOT NULL");
+        } else {
+            qb.push(" AND pc.reviewed_at IS NULL");
+        }
+    }
 }
@@ -0,0 +1,507 @@
+use serde::Serialize;
+
+/// Keyword(s) that, when present in changed text, put a floor under the
+/// severity a change is allowed to settle at - however small the token-level
+/// delta, a policy change that touches one of these concerns isn't "low".
+/// Substrings rather than whole words so "terminate"/"termination" and
+/// "license"/"licence" are both caught with one entry.
+const RISK_TERMS: &[(&str, Severity)] = &[
+    ("terminat", Severity::High),
+    ("data sharing", Severity::Critical),
+    ("share your data", Severity::Critical),
+    ("law enforcement", Severity::Critical),
+    ("arbitration", Severity::High),
+    ("licen", Severity::High),
+];
+
+/// Below this fraction of changed tokens, a change is treated as pure
+/// whitespace/formatting noise rather than a substantive edit.
+const LOW_CONFIDENCE_THRESHOLD: f64 = 0.05;
+/// Above this fraction, close to half the section (or more) changed.
+const HIGH_CONFIDENCE_THRESHOLD: f64 = 0.4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Severity at/above which a change is surfaced as needing member
+/// notification instead of just being recorded for the changelog.
+pub const NOTIFY_THRESHOLD: Severity = Severity::High;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionDiff {
+    pub heading: String,
+    pub added: usize,
+    pub removed: usize,
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChangeAnalysis {
+    pub affected_sections: Vec<SectionDiff>,
+    pub confidence_score: f64,
+    pub severity: Severity,
+}
+
+struct Section {
+    heading: String,
+    text: String,
+    tokens: Vec<String>,
+}
+
+/// Diffs `previous_text` against `current_text` at section granularity: both
+/// are split into sections on heading/blank-line boundaries, sections are
+/// aligned by heading (falling back to position for untitled/duplicate
+/// headings), and each aligned pair gets a token-level diff to count
+/// added/removed words. `confidence_score` is the fraction of changed tokens
+/// over the total tokens spanned by the diff, clamped to `[0, 1]`; `severity`
+/// starts from that fraction and is then boosted by any risk terms the
+/// changed text touches.
+pub fn analyze_change(previous_text: &str, current_text: &str) -> ChangeAnalysis {
+    let previous_sections = split_sections(previous_text);
+    let current_sections = split_sections(current_text);
+
+    let mut heading_index: std::collections::HashMap<&str, usize> =
+        std::collections::HashMap::new();
+    for (i, section) in previous_sections.iter().enumerate() {
+        if !section.heading.is_empty() {
+            heading_index.entry(section.heading.as_str()).or_insert(i);
+        }
+    }
+
+    let mut consumed = vec![false; previous_sections.len()];
+    let mut affected_sections = Vec::new();
+    let mut changed_tokens = 0usize;
+    let mut total_tokens = 0usize;
+    let mut changed_text = String::new();
+
+    for (idx, current) in current_sections.iter().enumerate() {
+        let by_heading = if current.heading.is_empty() {
+            None
+        } else {
+            heading_index.get(current.heading.as_str()).copied()
+        };
+        let previous_idx = by_heading
+            .filter(|&i| !consumed[i])
+            .or_else(|| (idx < previous_sections.len() && !consumed[idx]).then_some(idx));
+
+        let (previous_tokens, previous_text): (&[String], &str) = match previous_idx {
+            Some(i) => {
+                consumed[i] = true;
+                (&previous_sections[i].tokens, previous_sections[i].text.as_str())
+            }
+            None => (&[], ""),
+        };
+
+        let (added, removed, lcs_len) =
+            token_diff_bounded(previous_tokens, previous_text, &current.tokens, &current.text);
+        total_tokens += previous_tokens.len() + current.tokens.len() - lcs_len;
+        changed_tokens += added + removed;
+
+        if added > 0 || removed > 0 {
+            changed_text.push_str(&current.text);
+            changed_text.push('\n');
+            affected_sections.push(SectionDiff {
+                heading: section_label(&current.heading, idx),
+                added,
+                removed,
+                snippet: snippet_of(&current.text),
+            });
+        }
+    }
+
+    for (i, previous) in previous_sections.iter().enumerate() {
+        if consumed[i] {
+            continue;
+        }
+        total_tokens += previous.tokens.len();
+        changed_tokens += previous.tokens.len();
+        changed_text.push_str(&previous.text);
+        changed_text.push('\n');
+        affected_sections.push(SectionDiff {
+            heading: section_label(&previous.heading, i),
+            added: 0,
+            removed: previous.tokens.len(),
+            snippet: snippet_of(&previous.text),
+        });
+    }
+
+    let confidence_score = if total_tokens == 0 {
+        0.0
+    } else {
+        (changed_tokens as f64 / total_tokens as f64).clamp(0.0, 1.0)
+    };
+    let severity = derive_severity(confidence_score, &changed_text);
+
+    ChangeAnalysis {
+        affected_sections,
+        confidence_score,
+        severity,
+    }
+}
+
+fn section_label(heading: &str, index: usize) -> String {
+    if heading.is_empty() {
+        format!("section {}", index + 1)
+    } else {
+        heading.to_string()
+    }
+}
+
+fn derive_severity(confidence_score: f64, changed_text: &str) -> Severity {
+    let mut severity = if confidence_score < LOW_CONFIDENCE_THRESHOLD {
+        Severity::Low
+    } else if confidence_score < HIGH_CONFIDENCE_THRESHOLD {
+        Severity::Medium
+    } else {
+        Severity::High
+    };
+
+    let lower = changed_text.to_lowercase();
+    for (term, boost) in RISK_TERMS {
+        if *boost > severity && lower.contains(term) {
+            severity = *boost;
+        }
+    }
+
+    severity
+}
+
+/// Splits `text` into paragraphs on blank-line boundaries after normalizing
+/// whitespace, then groups consecutive paragraphs under the most recent line
+/// that looks like a heading so reflowed HTML-to-text output doesn't
+/// fragment a section into spurious extra pieces.
+fn split_sections(text: &str) -> Vec<Section> {
+    let normalized = normalize_whitespace(text);
+    let mut sections: Vec<(String, Vec<String>)> = Vec::new();
+    let mut current_heading = String::new();
+    let mut current_paragraphs: Vec<String> = Vec::new();
+
+    for paragraph in normalized.split("\n\n") {
+        let paragraph = paragraph.trim();
+        if paragraph.is_empty() {
+            continue;
+        }
+        let first_line = paragraph.lines().next().unwrap_or("");
+        if looks_like_heading(first_line) {
+            if !current_heading.is_empty() || !current_paragraphs.is_empty() {
+                sections.push((current_heading, current_paragraphs));
+            }
+            current_heading = first_line.trim_start_matches('#').trim().to_string();
+            current_paragraphs = paragraph
+                .lines()
+                .skip(1)
+                .map(|l| l.to_string())
+                .filter(|l| !l.is_empty())
+                .collect();
+        } else {
+            current_paragraphs.push(paragraph.to_string());
+        }
+    }
+    if !current_heading.is_empty() || !current_paragraphs.is_empty() {
+        sections.push((current_heading, current_paragraphs));
+    }
+    if sections.is_empty() {
+        sections.push((String::new(), vec![normalized.clone()]));
+    }
+
+    sections
+        .into_iter()
+        .map(|(heading, paragraphs)| {
+            let text = paragraphs.join("\n");
+            let tokens = text.split_whitespace().map(|t| t.to_string()).collect();
+            Section {
+                heading,
+                text,
+                tokens,
+            }
+        })
+        .collect()
+}
+
+/// A markdown `#` line, or a short (at most eight words) line that is
+/// all-uppercase or ends in `:`, is treated as a section heading rather than
+/// body text.
+fn looks_like_heading(line: &str) -> bool {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return false;
+    }
+    if trimmed.starts_with('#') {
+        return true;
+    }
+
+    let word_count = trimmed.split_whitespace().count();
+    if word_count == 0 || word_count > 8 {
+        return false;
+    }
+
+    let has_alphabetic = trimmed.chars().any(|c| c.is_alphabetic());
+    let all_uppercase = has_alphabetic
+        && trimmed
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .all(|c| c.is_uppercase());
+
+    all_uppercase || trimmed.ends_with(':')
+}
+
+/// Collapses runs of inline whitespace to single spaces and runs of blank
+/// lines to a single paragraph break, so differently-reflowed HTML-to-text
+/// output of otherwise-identical content doesn't register as a diff.
+fn normalize_whitespace(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank_pending = false;
+
+    for raw_line in text.lines() {
+        let line = raw_line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank_pending = true;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank_pending { "\n\n" } else { "\n" });
+        }
+        out.push_str(&line);
+        blank_pending = false;
+    }
+
+    out
+}
+
+fn snippet_of(text: &str) -> String {
+    const MAX_LEN: usize = 160;
+    if text.len() <= MAX_LEN {
+        return text.to_string();
+    }
+    let mut snippet: String = text.chars().take(MAX_LEN).collect();
+    snippet.push('\u{2026}');
+    snippet
+}
+
+/// Above this many `old.len() * new.len()` DP cells, `token_diff`'s O(n·m)
+/// table would run into the hundreds of megabytes or more - a realistic risk
+/// for a multi-thousand-word ToS page with no headings, which `split_sections`
+/// collapses into a single section spanning the whole document. Past the cap
+/// we fall back to the block-level diff, whose LCS table is sized by the
+/// number of sentence/paragraph blocks rather than tokens.
+const TOKEN_DIFF_CELL_CAP: usize = 4_000_000;
+
+/// Same contract as [`token_diff`], but bounds the O(n·m) DP table to
+/// [`TOKEN_DIFF_CELL_CAP`] cells, falling back to
+/// [`token_diff_via_block_fallback`] above that.
+fn token_diff_bounded(
+    old: &[String],
+    old_text: &str,
+    new: &[String],
+    new_text: &str,
+) -> (usize, usize, usize) {
+    match old.len().checked_mul(new.len()) {
+        Some(cells) if cells <= TOKEN_DIFF_CELL_CAP => token_diff(old, new),
+        _ => token_diff_via_block_fallback(old_text, new_text, old.len(), new.len()),
+    }
+}
+
+/// Stand-in for `token_diff` on oversized sections, built on top of the
+/// `analyzer_wasm::diff_blocks` block-level diff, whose LCS table spans
+/// blocks (sentences) rather than tokens.
+/// Whole added/removed blocks contribute their full token counts; a
+/// "modified" block (paired by `diff_blocks`'s own LCS-over-blocks alignment)
+/// contributes the portion of its shorter side implied by its Levenshtein
+/// similarity, with the rest counted as changed. `lcs_len` is then backed out
+/// from the totals rather than computed directly, since the whole point of
+/// this path is to avoid ever materializing an O(n·m) token table.
+fn token_diff_via_block_fallback(
+    old_text: &str,
+    new_text: &str,
+    old_len: usize,
+    new_len: usize,
+) -> (usize, usize, usize) {
+    let diff = analyzer_wasm::diff_blocks(old_text, new_text);
+
+    let mut added = block_token_count(&diff.added);
+    let mut removed = block_token_count(&diff.removed);
+
+    for modified in &diff.modified {
+        let old_tokens = modified.old.split_whitespace().count();
+        let new_tokens = modified.new.split_whitespace().count();
+        let unchanged = (old_tokens.min(new_tokens) as f64 * modified.similarity).round() as usize;
+        removed += old_tokens.saturating_sub(unchanged);
+        added += new_tokens.saturating_sub(unchanged);
+    }
+
+    let lcs_len = old_len.saturating_sub(removed).min(new_len.saturating_sub(added));
+    (added, removed, lcs_len)
+}
+
+fn block_token_count(blocks: &[String]) -> usize {
+    blocks.iter().map(|b| b.split_whitespace().count()).sum()
+}
+
+/// Computes `(added, removed, lcs_len)` for a token-level shortest-edit-script
+/// between `old` and `new`: the length of their longest common subsequence
+/// determines how many tokens match, and the rest are the added/removed
+/// counts a Myers-style SES would report.
+fn token_diff(old: &[String], new: &[String]) -> (usize, usize, usize) {
+    let n = old.len();
+    let m = new.len();
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if old[i] == new[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let lcs_len = table[0][0];
+    (new.len() - lcs_len, old.len() - lcs_len, lcs_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(text: &str) -> Vec<String> {
+        text.split_whitespace().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn looks_like_heading_accepts_markdown_all_caps_and_colon_lines() {
+        assert!(looks_like_heading("## Data Sharing"));
+        assert!(looks_like_heading("DATA SHARING"));
+        assert!(looks_like_heading("Data Sharing:"));
+    }
+
+    #[test]
+    fn looks_like_heading_rejects_ordinary_sentences_and_blank_lines() {
+        assert!(!looks_like_heading("We may share your data with partners."));
+        assert!(!looks_like_heading(""));
+        assert!(!looks_like_heading(
+            "This line has more than eight words so it cannot be a heading."
+        ));
+    }
+
+    #[test]
+    fn normalize_whitespace_collapses_inline_runs_and_blank_lines() {
+        let input = "Hello    world\n\n\n\nSecond   paragraph";
+        assert_eq!(normalize_whitespace(input), "Hello world\n\nSecond paragraph");
+    }
+
+    #[test]
+    fn token_diff_reports_zero_added_removed_for_identical_token_streams() {
+        let old = tokens("the quick brown fox");
+        let new = tokens("the quick brown fox");
+        assert_eq!(token_diff(&old, &new), (0, 0, 4));
+    }
+
+    #[test]
+    fn token_diff_counts_additions_and_removals_around_a_common_subsequence() {
+        let old = tokens("the quick brown fox");
+        let new = tokens("the quick red fox jumps");
+        // Common subsequence: "the quick fox" (len 3); "brown" removed,
+        // "red"/"jumps" added.
+        let (added, removed, lcs_len) = token_diff(&old, &new);
+        assert_eq!((added, removed, lcs_len), (2, 1, 3));
+    }
+
+    #[test]
+    fn derive_severity_respects_confidence_thresholds_with_no_risk_terms() {
+        assert_eq!(derive_severity(0.0, "harmless wording tweak"), Severity::Low);
+        assert_eq!(derive_severity(0.2, "harmless wording tweak"), Severity::Medium);
+        assert_eq!(derive_severity(0.9, "harmless wording tweak"), Severity::High);
+    }
+
+    #[test]
+    fn derive_severity_boosts_but_never_downgrades_for_risk_terms() {
+        // A low-confidence edit that touches a risk term is boosted up to
+        // that term's floor...
+        assert_eq!(
+            derive_severity(0.0, "Your account may be terminated without notice"),
+            Severity::High
+        );
+        // ...but a risk term never pulls an already-higher severity back
+        // down: once "share your data" has lifted a change to Critical, the
+        // later-checked "license" term (floor High) leaves it at Critical.
+        assert_eq!(
+            derive_severity(0.9, "We may share your data under our new license terms"),
+            Severity::Critical
+        );
+    }
+
+    #[test]
+    fn analyze_change_aligns_sections_by_heading_and_reports_per_section_diffs() {
+        let previous = "PRIVACY\nWe keep your data for 30 days.\n\nCONTACT\nEmail us at support@example.com.";
+        let current = "PRIVACY\nWe keep your data for 90 days and may share your data with partners.\n\nCONTACT\nEmail us at support@example.com.";
+
+        let analysis = analyze_change(previous, current);
+
+        assert_eq!(analysis.affected_sections.len(), 1);
+        assert_eq!(analysis.affected_sections[0].heading, "PRIVACY");
+        assert!(analysis.affected_sections[0].added > 0);
+        assert!(analysis.confidence_score > 0.0);
+        assert_eq!(analysis.severity, Severity::Critical);
+    }
+
+    #[test]
+    fn analyze_change_reports_no_sections_or_changes_for_identical_text() {
+        // Stands in for the first-ever-snapshot case handled structurally in
+        // `platforms::record_snapshot` (there's simply no previous text to
+        // diff against yet): with nothing to compare, there is nothing to
+        // report as changed.
+        let text = "TERMS\nYou agree to use the service responsibly.";
+
+        let analysis = analyze_change(text, text);
+
+        assert!(analysis.affected_sections.is_empty());
+        assert_eq!(analysis.confidence_score, 0.0);
+        assert_eq!(analysis.severity, Severity::Low);
+    }
+
+    #[test]
+    fn token_diff_bounded_falls_back_to_block_diff_above_the_cell_cap() {
+        // Below the cap, the bounded wrapper just delegates to the direct DP
+        // table.
+        let old = tokens("the quick brown fox");
+        let new = tokens("the quick red fox jumps");
+        assert_eq!(
+            token_diff_bounded(&old, "the quick brown fox", &new, "the quick red fox jumps"),
+            token_diff(&old, &new)
+        );
+
+        // Above the cap, `old.len() * new.len()` alone is enough to route to
+        // the block-diff fallback instead of ever materializing an O(n*m)
+        // table. `n` is just over sqrt(TOKEN_DIFF_CELL_CAP) so `n * n`
+        // exceeds the cap while staying cheap to build in a test.
+        let n = 2001;
+        assert!(n * n > TOKEN_DIFF_CELL_CAP);
+        let old_tokens = vec!["aaa".to_string(); n];
+        let new_tokens = vec!["bbb".to_string(); n];
+        let old_text = old_tokens.join(" ");
+        let new_text = new_tokens.join(" ");
+
+        let (added, removed, lcs_len) =
+            token_diff_bounded(&old_tokens, &old_text, &new_tokens, &new_text);
+        assert_eq!(removed, n);
+        assert_eq!(added, n);
+        assert_eq!(lcs_len, 0);
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::AppState;
+
+/// Redis stream that collection events are appended to. Using a stream
+/// (rather than plain pub/sub) means a client that reconnects can replay
+/// anything that fired while it was away instead of silently losing it.
+pub const POLICY_CHANGES_STREAM: &str = "policy-changes";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyChangeEvent {
+    pub platform_id: Uuid,
+    pub platform: String,
+    pub document_type: String,
+    pub url: String,
+    pub old_checksum: Option<String>,
+    pub new_checksum: String,
+    pub snapshot_id: Uuid,
+    pub severity: String,
+}
+
+/// Appends a policy-change event onto the [`POLICY_CHANGES_STREAM`] so SSE
+/// subscribers in `handlers::stream_changes` see it in near real time.
+pub async fn publish_change_event(state: &AppState, event: &PolicyChangeEvent) -> Result<()> {
+    let payload = serde_json::to_string(event)?;
+    let mut conn = state.redis.get_multiplexed_async_connection().await?;
+    let _id: String = conn
+        .xadd(POLICY_CHANGES_STREAM, "*", &[("event", payload.as_str())])
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::AppState;
+
+/// The validators (and resulting content checksum) from a `PolicyDocument`'s
+/// last successful fetch, cached in Redis keyed by document id so the next
+/// collection cycle can send `If-None-Match`/`If-Modified-Since` and skip
+/// re-downloading and re-hashing content that hasn't changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFetch {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub checksum: String,
+}
+
+fn cache_key(document_id: Uuid) -> String {
+    format!("fetch-cache:{document_id}")
+}
+
+/// Returns the cached validators for `document_id`, or `None` if there's
+/// nothing cached (or the cached entry expired, per `fetch_cache_ttl_secs`).
+pub async fn get(state: &AppState, document_id: Uuid) -> Result<Option<CachedFetch>> {
+    let mut conn = state.redis.get_multiplexed_async_connection().await?;
+    let raw: Option<String> = conn.get(cache_key(document_id)).await?;
+    Ok(match raw {
+        Some(raw) => serde_json::from_str(&raw)?,
+        None => None,
+    })
+}
+
+/// Stores `cached` for `document_id`, expiring after `fetch_cache_ttl_secs`
+/// so a document whose server stops sending validators still gets a full
+/// re-fetch eventually.
+pub async fn put(state: &AppState, document_id: Uuid, cached: &CachedFetch) -> Result<()> {
+    let mut conn = state.redis.get_multiplexed_async_connection().await?;
+    let payload = serde_json::to_string(cached)?;
+    let ttl = state.config.collector.fetch_cache_ttl_secs;
+    conn.set_ex::<_, _, ()>(cache_key(document_id), payload, ttl)
+        .await?;
+    Ok(())
+}
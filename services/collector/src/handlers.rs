@@ -1,17 +1,32 @@
+use std::convert::Infallible;
+
+use async_stream::stream;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
     response::IntoResponse,
     Json,
 };
+use futures_util::Stream;
+use redis::streams::{StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+use serde::Deserialize;
 use serde_json::json;
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::{db, models::CollectionJob, platforms, AppState};
+use chrono::{DateTime, Utc};
+
+use crate::{
+    events::{self, PolicyChangeEvent},
+    maintenance,
+    models::{ChangeAnalyticsFilter, CollectionJob},
+    platforms, AppState,
+};
 
 pub async fn list_platforms(State(state): State<AppState>) -> impl IntoResponse {
-    match db::get_active_platforms(&state.db).await {
+    match state.db.get_active_platforms().await {
         Ok(platforms) => (StatusCode::OK, Json(json!({ "platforms": platforms }))),
         Err(e) => {
             error!("Failed to fetch platforms: {}", e);
@@ -27,7 +42,7 @@ pub async fn get_platform(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match db::get_platform_by_id(&state.db, id).await {
+    match state.db.get_platform_by_id(id).await {
         Ok(Some(platform)) => (StatusCode::OK, Json(json!({ "platform": platform }))),
         Ok(None) => (
             StatusCode::NOT_FOUND,
@@ -43,13 +58,23 @@ pub async fn get_platform(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TriggerCollectionQuery {
+    #[serde(default)]
+    force_refresh: bool,
+}
+
 pub async fn trigger_collection(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<TriggerCollectionQuery>,
 ) -> impl IntoResponse {
-    info!("Manual collection triggered for platform {}", id);
+    info!(
+        "Manual collection triggered for platform {} (force_refresh={})",
+        id, query.force_refresh
+    );
 
-    match db::get_platform_by_id(&state.db, id).await {
+    match state.db.get_platform_by_id(id).await {
         Ok(Some(platform)) => {
             // Queue collection job
             let job = CollectionJob {
@@ -66,7 +91,9 @@ pub async fn trigger_collection(
 
             // In a real implementation, this would be queued in Redis
             // For now, we'll run it synchronously
-            match platforms::collect_platform_policies(&state, &platform).await {
+            match platforms::collect_platform_policies_with(&state, &platform, query.force_refresh)
+                .await
+            {
                 Ok(result) => {
                     info!(
                         "Collection completed for {}: {} documents, {} changes",
@@ -111,7 +138,7 @@ pub async fn trigger_collection(
 }
 
 pub async fn list_changes(State(state): State<AppState>) -> impl IntoResponse {
-    match db::get_recent_changes(&state.db, 100).await {
+    match state.db.get_recent_changes(100).await {
         Ok(changes) => (StatusCode::OK, Json(json!({ "changes": changes }))),
         Err(e) => {
             error!("Failed to fetch changes: {}", e);
@@ -123,11 +150,120 @@ pub async fn list_changes(State(state): State<AppState>) -> impl IntoResponse {
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct StreamChangesQuery {
+    platform_id: Option<Uuid>,
+    severity: Option<String>,
+}
+
+impl StreamChangesQuery {
+    fn matches(&self, event: &PolicyChangeEvent) -> bool {
+        self.platform_id.map_or(true, |id| id == event.platform_id)
+            && self
+                .severity
+                .as_deref()
+                .map_or(true, |severity| severity.eq_ignore_ascii_case(&event.severity))
+    }
+}
+
+/// Streams policy-change events as Server-Sent Events, backed by the
+/// `policy-changes` Redis stream. On connect it replays anything the client
+/// missed since the `Last-Event-ID` header (defaulting to the start of the
+/// stream), then tails new entries with a blocking `XREAD`, using each
+/// entry's Redis stream id as the SSE event id so a reconnecting client can
+/// resume exactly where it left off. Pass `?platform_id=` and/or
+/// `?severity=` to only receive events matching both (dashboards and
+/// notification bots that only care about one platform or severity don't
+/// have to filter client-side).
+pub async fn stream_changes(
+    State(state): State<AppState>,
+    Query(filter): Query<StreamChangesQuery>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_id = headers
+        .get("Last-Event-ID")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "0".to_string());
+
+    let stream = stream! {
+        let mut conn = match state.redis.get_multiplexed_async_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                error!("Failed to open Redis connection for change stream: {}", e);
+                return;
+            }
+        };
+
+        let mut cursor = last_id;
+
+        let replay: redis::RedisResult<Vec<(String, Vec<(String, String)>)>> = conn
+            .xrange(events::POLICY_CHANGES_STREAM, format!("({cursor}"), "+")
+            .await;
+
+        if let Ok(entries) = replay {
+            for (id, fields) in entries {
+                if let Some((_, payload)) = fields.into_iter().find(|(k, _)| k == "event") {
+                    cursor = id.clone();
+                    if matches_filter(&filter, &payload) {
+                        yield Ok(Event::default().id(id).data(payload));
+                    }
+                }
+            }
+        }
+
+        loop {
+            let opts = StreamReadOptions::default().block(15_000);
+            let reply: redis::RedisResult<StreamReadReply> = conn
+                .xread_options(&[events::POLICY_CHANGES_STREAM], &[cursor.as_str()], &opts)
+                .await;
+
+            match reply {
+                Ok(reply) if !reply.keys.is_empty() => {
+                    for key in reply.keys {
+                        for id_entry in key.ids {
+                            cursor = id_entry.id.clone();
+                            let payload = id_entry.map.get("event").and_then(|v| match v {
+                                redis::Value::Data(bytes) => String::from_utf8(bytes.clone()).ok(),
+                                _ => None,
+                            });
+                            if let Some(payload) = payload {
+                                if matches_filter(&filter, &payload) {
+                                    yield Ok(Event::default().id(id_entry.id).data(payload));
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(_) => {
+                    // Nothing new before the block timeout elapsed - send a
+                    // comment so intermediate proxies don't drop the connection.
+                    yield Ok(Event::default().comment("keep-alive"));
+                }
+                Err(e) => {
+                    error!("Error reading policy change stream: {}", e);
+                    break;
+                }
+            }
+        }
+    };
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// `false` if `payload` doesn't even deserialize - a malformed entry is
+/// filtered out rather than sent to every client regardless of their filter.
+fn matches_filter(filter: &StreamChangesQuery, payload: &str) -> bool {
+    serde_json::from_str::<PolicyChangeEvent>(payload)
+        .map(|event| filter.matches(&event))
+        .unwrap_or(false)
+}
+
 pub async fn get_change(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match db::get_change_by_id(&state.db, id).await {
+    match state.db.get_change_by_id(id).await {
         Ok(Some(change)) => (StatusCode::OK, Json(json!({ "change": change }))),
         Ok(None) => (
             StatusCode::NOT_FOUND,
@@ -142,3 +278,112 @@ pub async fn get_change(
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsQuery {
+    platform_id: Option<Uuid>,
+    document_type: Option<String>,
+    change_type: Option<String>,
+    severity: Option<String>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    false_positive: Option<bool>,
+    reviewed: Option<bool>,
+}
+
+impl From<AnalyticsQuery> for ChangeAnalyticsFilter {
+    fn from(query: AnalyticsQuery) -> Self {
+        ChangeAnalyticsFilter {
+            platform_id: query.platform_id,
+            document_type: query.document_type,
+            change_type: query.change_type,
+            severity: query.severity,
+            from: query.from,
+            to: query.to,
+            false_positive: query.false_positive,
+            reviewed: query.reviewed,
+        }
+    }
+}
+
+/// Grouped counts and summary statistics over `policy_changes`, built from
+/// whichever of `platform_id`, `document_type`, `change_type`, `severity`,
+/// `from`/`to`, `false_positive`, and `reviewed` are supplied on the query
+/// string - lets a dashboard slice the change history without pulling every
+/// row and aggregating client-side.
+pub async fn get_change_analytics(
+    State(state): State<AppState>,
+    Query(query): Query<AnalyticsQuery>,
+) -> impl IntoResponse {
+    let filter: ChangeAnalyticsFilter = query.into();
+    match state.db.get_change_analytics(&filter).await {
+        Ok(analytics) => (StatusCode::OK, Json(json!({ "analytics": analytics }))),
+        Err(e) => {
+            error!("Failed to fetch change analytics: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to fetch change analytics" })),
+            )
+        }
+    }
+}
+
+pub async fn list_maintenance_runs(State(state): State<AppState>) -> impl IntoResponse {
+    match state.db.list_maintenance_runs(50).await {
+        Ok(runs) => (StatusCode::OK, Json(json!({ "runs": runs }))),
+        Err(e) => {
+            error!("Failed to fetch maintenance runs: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to fetch maintenance runs" })),
+            )
+        }
+    }
+}
+
+/// Archives documents past the configured retention window and prunes every
+/// document's snapshots older than it, keeping each document's latest one.
+/// See `crate::maintenance::run_retention`.
+pub async fn run_maintenance_retention(State(state): State<AppState>) -> impl IntoResponse {
+    let retention_days = state.config.maintenance.retention_days;
+    match maintenance::run_retention(&state, retention_days).await {
+        Ok(run) => (StatusCode::OK, Json(json!({ "run": run }))),
+        Err(e) => {
+            error!("Retention maintenance run failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Retention run failed" })),
+            )
+        }
+    }
+}
+
+/// Runs `VACUUM ANALYZE` over the collector's fast-growing tables. See
+/// `crate::maintenance::run_vacuum`.
+pub async fn run_maintenance_vacuum(State(state): State<AppState>) -> impl IntoResponse {
+    match maintenance::run_vacuum(&state).await {
+        Ok(run) => (StatusCode::OK, Json(json!({ "run": run }))),
+        Err(e) => {
+            error!("Vacuum maintenance run failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Vacuum run failed" })),
+            )
+        }
+    }
+}
+
+/// Recomputes checksums over every document's current snapshot and flags
+/// mismatches. See `crate::maintenance::run_verification`.
+pub async fn run_maintenance_verify(State(state): State<AppState>) -> impl IntoResponse {
+    match maintenance::run_verification(&state).await {
+        Ok(run) => (StatusCode::OK, Json(json!({ "run": run }))),
+        Err(e) => {
+            error!("Verification maintenance run failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Verification run failed" })),
+            )
+        }
+    }
+}
@@ -17,19 +17,29 @@ use tracing::{error, info};
 
 mod config;
 mod db;
+mod diff;
+mod events;
+mod fetch_cache;
 mod handlers;
+mod maintenance;
 mod models;
 mod platforms;
+mod queue;
 mod scheduler;
 mod scraper;
+mod signing;
+mod storage;
 
 use config::Config;
+use db::Repository;
+use storage::SnapshotStorage;
 
 #[derive(Clone)]
 struct AppState {
-    db: sqlx::PgPool,
+    db: Arc<dyn Repository>,
     redis: redis::Client,
     config: Arc<Config>,
+    storage: Arc<dyn SnapshotStorage>,
 }
 
 #[tokio::main]
@@ -50,28 +60,37 @@ async fn main() -> anyhow::Result<()> {
     info!("Configuration loaded");
 
     // Connect to PostgreSQL
-    let db = PgPoolOptions::new()
+    let pg_pool = PgPoolOptions::new()
         .max_connections(config.database.max_connections)
         .connect(&config.database.url)
         .await?;
     info!("Connected to PostgreSQL");
 
+    let db: Arc<dyn Repository> = Arc::new(db::PostgresRepository::new(pg_pool));
+
     // Connect to Redis
     let redis = redis::Client::open(config.redis.url.as_str())?;
     let mut redis_conn = redis.get_connection()?;
     redis::cmd("PING").query::<String>(&mut redis_conn)?;
     info!("Connected to Redis");
 
+    // Build the configured snapshot storage backend
+    let storage = storage::build_storage(&config.storage)?;
+    info!("Snapshot storage backend: {}", config.storage.backend);
+
     // Build application state
     let state = AppState {
         db: db.clone(),
         redis: redis.clone(),
         config: config.clone(),
+        storage,
     };
 
-    // Start background scheduler
+    // Start background scheduler and the durable job queue worker that
+    // actually performs the collection runs it enqueues
     let scheduler = scheduler::start_scheduler(state.clone()).await?;
-    info!("Background scheduler started");
+    scheduler::start_worker(state.clone());
+    info!("Background scheduler and job queue worker started");
 
     // Build router
     let app = create_router(state);
@@ -96,7 +115,13 @@ fn create_router(state: AppState) -> Router {
         .route("/api/platforms/:id", get(handlers::get_platform))
         .route("/api/platforms/:id/collect", post(handlers::trigger_collection))
         .route("/api/changes", get(handlers::list_changes))
+        .route("/api/changes/stream", get(handlers::stream_changes))
+        .route("/api/changes/analytics", get(handlers::get_change_analytics))
         .route("/api/changes/:id", get(handlers::get_change))
+        .route("/api/maintenance/runs", get(handlers::list_maintenance_runs))
+        .route("/api/maintenance/retention", post(handlers::run_maintenance_retention))
+        .route("/api/maintenance/vacuum", post(handlers::run_maintenance_vacuum))
+        .route("/api/maintenance/verify", post(handlers::run_maintenance_verify))
         .layer(TraceLayer::new_for_http())
         .with_state(state)
 }
@@ -0,0 +1,91 @@
+use anyhow::Result;
+use tracing::{info, warn};
+
+use crate::{
+    models::{MaintenanceRun, PolicySnapshot},
+    AppState,
+};
+
+pub const RETENTION_OPERATION: &str = "retention";
+pub const VACUUM_OPERATION: &str = "vacuum";
+pub const VERIFY_OPERATION: &str = "verify";
+
+/// Archives policy documents that haven't been seen in `retention_days` and
+/// prunes snapshot rows past the same window, always keeping each
+/// document's most recent snapshot so its current content is never pruned
+/// out from under it.
+pub async fn run_retention(state: &AppState, retention_days: i64) -> Result<MaintenanceRun> {
+    let run = state.db.start_maintenance_run(RETENTION_OPERATION).await?;
+
+    let outcome: Result<u64> = async {
+        let archived = state.db.archive_stale_documents(retention_days).await?;
+        let pruned = state.db.prune_old_snapshots(retention_days).await?;
+        Ok(archived + pruned)
+    }
+    .await;
+
+    finish_run(state, run, outcome).await
+}
+
+/// Runs `VACUUM ANALYZE` over the tables that grow with every collection
+/// cycle, so their planner statistics and bloat don't rely on autovacuum
+/// alone.
+pub async fn run_vacuum(state: &AppState) -> Result<MaintenanceRun> {
+    let run = state.db.start_maintenance_run(VACUUM_OPERATION).await?;
+    let outcome = state.db.vacuum_analyze_tables().await.map(|_| 0u64);
+    finish_run(state, run, outcome).await
+}
+
+/// Recomputes `PolicySnapshot::calculate_checksum` over each document's
+/// current snapshot and flags any whose stored `checksum` no longer
+/// matches, catching storage-backend corruption or partial writes that
+/// would otherwise go unnoticed until a human read the blob.
+pub async fn run_verification(state: &AppState) -> Result<MaintenanceRun> {
+    let run = state.db.start_maintenance_run(VERIFY_OPERATION).await?;
+    let outcome = verify_checksums(state).await;
+    finish_run(state, run, outcome).await
+}
+
+async fn verify_checksums(state: &AppState) -> Result<u64> {
+    let snapshots = state.db.current_snapshots().await?;
+    let mut mismatches = 0u64;
+
+    for snapshot in snapshots {
+        let content = match state.storage.get(&snapshot.storage_key).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Could not read snapshot {} from storage: {}", snapshot.id, e);
+                continue;
+            }
+        };
+
+        let recomputed = PolicySnapshot::calculate_checksum(&String::from_utf8_lossy(&content));
+        if recomputed != snapshot.checksum {
+            warn!(
+                "Checksum mismatch for snapshot {}: stored {} recomputed {}",
+                snapshot.id, snapshot.checksum, recomputed
+            );
+            state.db.flag_checksum_mismatch(snapshot.id).await?;
+            mismatches += 1;
+        }
+    }
+
+    Ok(mismatches)
+}
+
+async fn finish_run(state: &AppState, run: MaintenanceRun, outcome: Result<u64>) -> Result<MaintenanceRun> {
+    match outcome {
+        Ok(affected) => {
+            info!(
+                "Maintenance run {} ({}) affected {} row(s)",
+                run.id, run.operation, affected
+            );
+            state.db.complete_maintenance_run(run.id, affected as i64).await
+        }
+        Err(e) => {
+            warn!("Maintenance run {} ({}) failed: {}", run.id, run.operation, e);
+            state.db.fail_maintenance_run(run.id, &e.to_string()).await?;
+            Err(e)
+        }
+    }
+}
@@ -11,6 +11,10 @@ pub struct Platform {
     pub url: String,
     pub api_endpoint: Option<String>,
     pub api_enabled: bool,
+    /// Whether outbound fetches for this platform must carry an HTTP
+    /// Signature (see `crate::signing`) - true for Fediverse instances that
+    /// reject unsigned GETs.
+    pub requires_http_signature: bool,
     pub scraping_enabled: bool,
     pub monitoring_active: bool,
     pub check_frequency_minutes: i32,
@@ -45,9 +49,12 @@ pub struct PolicySnapshot {
     pub id: Uuid,
     pub policy_document_id: Uuid,
     pub captured_at: DateTime<Utc>,
-    pub content_text: String,
-    pub content_html: Option<String>,
-    pub content_markdown: Option<String>,
+    /// Which `SnapshotStorage` backend the full content lives in (see
+    /// `crate::storage`) - the row itself never holds the raw text.
+    pub storage_backend: String,
+    /// Key within that backend; equal to `checksum` so identical content
+    /// across documents dedupes onto the same blob.
+    pub storage_key: String,
     pub word_count: Option<i32>,
     pub char_count: Option<i32>,
     pub checksum: String,
@@ -79,6 +86,74 @@ pub struct PolicyChange {
     pub metadata: serde_json::Value,
 }
 
+/// Composable filters for `Repository::get_change_analytics`, built from the
+/// `/api/changes/analytics` query string. `None` means "don't filter on
+/// this field".
+#[derive(Debug, Clone, Default)]
+pub struct ChangeAnalyticsFilter {
+    pub platform_id: Option<Uuid>,
+    pub document_type: Option<String>,
+    pub change_type: Option<String>,
+    pub severity: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub false_positive: Option<bool>,
+    /// `Some(true)` for changes with a `reviewed_at`, `Some(false)` for
+    /// those still awaiting review.
+    pub reviewed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct PlatformChangeCount {
+    pub platform_id: Uuid,
+    pub platform_name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SeverityChangeCount {
+    pub severity: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WeeklyChangeCount {
+    pub week_start: DateTime<Utc>,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ChangeAnalyticsSummary {
+    pub total_changes: i64,
+    /// `None` if no change in the filtered set has been reviewed yet.
+    pub mean_time_to_review_secs: Option<f64>,
+    /// Fraction (`[0, 1]`) of filtered changes flagged
+    /// `requires_member_notification`.
+    pub notification_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeAnalytics {
+    pub by_platform: Vec<PlatformChangeCount>,
+    pub by_severity: Vec<SeverityChangeCount>,
+    pub by_week: Vec<WeeklyChangeCount>,
+    pub summary: ChangeAnalyticsSummary,
+}
+
+/// One invocation of a `crate::maintenance` operation (retention, vacuum, or
+/// checksum verification), recorded so an operator can see maintenance
+/// history in `/api/maintenance/runs` rather than running raw SQL by hand.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MaintenanceRun {
+    pub id: Uuid,
+    pub operation: String,
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub affected_rows: i64,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionJob {
     pub platform_id: Uuid,
@@ -1,11 +1,32 @@
 use anyhow::Result;
+use analyzer_wasm::BlockDiff;
 use tracing::{info, warn};
 
-use crate::{db, models::{CollectionResult, Platform, PolicySnapshot}, scraper, AppState};
+use crate::{
+    diff,
+    events,
+    events::PolicyChangeEvent,
+    fetch_cache,
+    fetch_cache::CachedFetch,
+    models::{CollectionResult, Platform, PolicySnapshot},
+    scraper, AppState,
+};
 
 pub async fn collect_platform_policies(
     state: &AppState,
     platform: &Platform,
+) -> Result<Vec<CollectionResult>> {
+    collect_platform_policies_with(state, platform, false).await
+}
+
+/// Like [`collect_platform_policies`], but `force_refresh` bypasses the
+/// conditional-fetch cache (see `crate::fetch_cache`) and re-downloads every
+/// document unconditionally - wired up to the `?force_refresh=` flag on the
+/// manual `/api/platforms/:id/collect` handler.
+pub async fn collect_platform_policies_with(
+    state: &AppState,
+    platform: &Platform,
+    force_refresh: bool,
 ) -> Result<Vec<CollectionResult>> {
     info!("Starting collection for platform: {}", platform.name);
 
@@ -18,93 +39,326 @@ pub async fn collect_platform_policies(
 
     // Collect policy documents
     for url in policy_urls {
-        if let Ok(result) = collect_document(state, platform, &url, "policy").await {
+        if let Ok(result) = collect_document(state, platform, &url, "policy", force_refresh).await {
             results.push(result);
         }
     }
 
     // Collect terms documents
     for url in terms_urls {
-        if let Ok(result) = collect_document(state, platform, &url, "terms").await {
+        if let Ok(result) = collect_document(state, platform, &url, "terms", force_refresh).await {
             results.push(result);
         }
     }
 
     // Collect community guidelines
     for url in community_urls {
-        if let Ok(result) = collect_document(state, platform, &url, "community_guidelines").await {
+        if let Ok(result) =
+            collect_document(state, platform, &url, "community_guidelines", force_refresh).await
+        {
             results.push(result);
         }
     }
 
+    // Fediverse instances don't publish their policy/terms URLs up front -
+    // they're discovered per-instance via WebFinger + NodeInfo + ActivityPub,
+    // so each configured handle is resolved before being fed through the
+    // normal collect_document snapshot + change-detection flow.
+    if platform.name == "fediverse" {
+        for handle in extract_urls(&platform.metadata.get("instances").cloned().unwrap_or_default())? {
+            match collect_fediverse_instance(state, platform, &handle).await {
+                Ok(mut instance_results) => results.append(&mut instance_results),
+                Err(e) => warn!("Fediverse discovery failed for {}: {}", handle, e),
+            }
+        }
+
+        // Mastodon/Pleroma instances also expose their rules natively via
+        // the instance API, which is both cheaper and more reliable than
+        // scraping whatever HTML page they render them on.
+        if platform.api_enabled && platform.api_endpoint.is_some() {
+            match collect_fediverse_instance_rules(state, platform).await {
+                Ok(result) => results.push(result),
+                Err(e) => warn!("Fediverse instance API collection failed for {}: {}", platform.name, e),
+            }
+        }
+    }
+
     // Update last checked timestamp
-    db::update_platform_last_checked(&state.db, platform.id).await?;
+    state.db.update_platform_last_checked(platform.id).await?;
 
     info!("Collection completed for {}: {} documents", platform.name, results.len());
     Ok(results)
 }
 
+/// Resolves a single Fediverse `acct:user@host` (or bare host) handle via
+/// WebFinger/NodeInfo/ActivityPub discovery, then runs each policy/terms URL
+/// it surfaces through the ordinary `collect_document` pipeline.
+async fn collect_fediverse_instance(
+    state: &AppState,
+    platform: &Platform,
+    handle: &str,
+) -> Result<Vec<CollectionResult>> {
+    let discovery = scraper::discover_fediverse_instance(state, handle).await?;
+    info!(
+        "Discovered fediverse instance {} ({} policy URL(s))",
+        discovery.actor_url,
+        discovery.policy_urls.len()
+    );
+
+    let mut results = Vec::new();
+    for url in &discovery.policy_urls {
+        let content = scraper::fetch_via_scraper(state, url).await?;
+        let result = record_snapshot(state, platform, url, "policy", &content, "fediverse_discovery").await?;
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Fetches a Fediverse instance's published rules via its Mastodon-compatible
+/// instance API (see `scraper::fetch_mastodon_instance_rules`) and records
+/// them as an `instance_rules` document, bypassing HTML scraping entirely.
+async fn collect_fediverse_instance_rules(
+    state: &AppState,
+    platform: &Platform,
+) -> Result<CollectionResult> {
+    let content = scraper::fetch_mastodon_instance_rules(state, platform).await?;
+    let url = format!(
+        "{}/api/v1/instance",
+        platform.api_endpoint.as_deref().unwrap_or_default()
+    );
+    record_snapshot(state, platform, &url, "instance_rules", &content, "fediverse_api").await
+}
+
 async fn collect_document(
     state: &AppState,
     platform: &Platform,
     url: &str,
     document_type: &str,
+    force_refresh: bool,
 ) -> Result<CollectionResult> {
     info!("Collecting {} from {}", document_type, url);
 
-    // Fetch content using scraper
-    let content = if platform.api_enabled {
-        scraper::fetch_via_api(state, platform, url).await?
+    if platform.api_enabled {
+        let content = scraper::fetch_via_api(state, platform, url).await?;
+        return record_snapshot(state, platform, url, document_type, &content, "api").await;
+    }
+
+    // Create/update the document row up front so the fetch cache has an id
+    // to key off, and so a 304 below still has something to bump
+    // `last_seen_at` on.
+    let doc = state
+        .db
+        .create_or_update_policy_document(platform.id, document_type, url, None)
+        .await?;
+
+    let cached = if force_refresh {
+        None
     } else {
-        scraper::fetch_via_scraper(state, url).await?
+        fetch_cache::get(state, doc.id).await.unwrap_or_else(|e| {
+            warn!("Failed to read fetch cache for document {}: {}", doc.id, e);
+            None
+        })
     };
 
+    let outcome = scraper::fetch_via_scraper_conditional(
+        state,
+        url,
+        cached.as_ref().and_then(|c| c.etag.as_deref()),
+        cached.as_ref().and_then(|c| c.last_modified.as_deref()),
+    )
+    .await?;
+
+    match outcome {
+        scraper::FetchOutcome::NotModified => {
+            // `fetch_via_scraper_conditional` only looks at the HTTP status
+            // code, so a misbehaving CDN/reverse proxy can hand us a 304
+            // even though `cached` was `None` (first-ever collection,
+            // `force_refresh`, or a Redis read failure) and we never sent a
+            // validator to begin with. The server doesn't actually
+            // guarantee the invariant a 304 implies, so fall back instead
+            // of trusting it blindly.
+            let checksum = match cached {
+                Some(cached) => cached.checksum,
+                None => match state.db.get_latest_snapshot(doc.id).await? {
+                    Some(snapshot) => {
+                        warn!(
+                            "{} for {} returned 304 Not Modified without us having sent a cache validator; trusting the existing snapshot",
+                            document_type, platform.name
+                        );
+                        snapshot.checksum
+                    }
+                    None => {
+                        warn!(
+                            "{} for {} returned 304 Not Modified with no validator sent and no prior snapshot to fall back on; forcing a full re-fetch",
+                            document_type, platform.name
+                        );
+                        let content = scraper::fetch_via_scraper(state, url).await?;
+                        return record_snapshot(state, platform, url, document_type, &content, "scraper").await;
+                    }
+                },
+            };
+            info!(
+                "{} unchanged (304 Not Modified) for {}, skipping re-collection",
+                document_type, platform.name
+            );
+            let latest_snapshot_id = state.db.get_latest_snapshot(doc.id).await?.map(|s| s.id);
+            Ok(CollectionResult {
+                platform_id: platform.id,
+                document_id: doc.id,
+                snapshot_id: latest_snapshot_id.unwrap_or(doc.id),
+                content: String::new(),
+                checksum: checksum.clone(),
+                change_detected: false,
+                previous_checksum: Some(checksum),
+            })
+        }
+        scraper::FetchOutcome::Modified {
+            content,
+            etag,
+            last_modified,
+        } => {
+            let checksum = PolicySnapshot::calculate_checksum(&content);
+            if let Err(e) = fetch_cache::put(
+                state,
+                doc.id,
+                &CachedFetch {
+                    etag,
+                    last_modified,
+                    checksum,
+                },
+            )
+            .await
+            {
+                warn!("Failed to update fetch cache for document {}: {}", doc.id, e);
+            }
+            record_snapshot(state, platform, url, document_type, &content, "scraper").await
+        }
+    }
+}
+
+async fn record_snapshot(
+    state: &AppState,
+    platform: &Platform,
+    url: &str,
+    document_type: &str,
+    content: &str,
+    capture_method: &str,
+) -> Result<CollectionResult> {
     // Calculate checksum
     let checksum = PolicySnapshot::calculate_checksum(&content);
+    let word_count = PolicySnapshot::calculate_word_count(&content);
+    let char_count = PolicySnapshot::calculate_char_count(&content);
 
     // Create or update policy document record
-    let doc = db::create_or_update_policy_document(
-        &state.db,
-        platform.id,
-        document_type,
-        url,
-        None,
-    )
-    .await?;
+    let doc = state
+        .db
+        .create_or_update_policy_document(platform.id, document_type, url, None)
+        .await?;
 
-    // Get previous snapshot to check for changes
-    let previous_snapshot = db::get_latest_snapshot(&state.db, doc.id).await?;
+    // Get previous snapshot to check for changes. A first-ever snapshot for
+    // this document is never a "change" - there's nothing to diff it against.
+    let previous_snapshot = state.db.get_latest_snapshot(doc.id).await?;
+    let previous_snapshot_id = previous_snapshot.as_ref().map(|s| s.id);
     let previous_checksum = previous_snapshot.as_ref().map(|s| s.checksum.clone());
-    let change_detected = previous_checksum.as_ref() != Some(&checksum);
+    let change_detected = previous_snapshot.is_some() && previous_checksum.as_deref() != Some(checksum.as_str());
 
-    // Create new snapshot
-    let snapshot = db::create_policy_snapshot(
-        &state.db,
-        doc.id,
-        &content,
-        None,
-        &checksum,
-        if platform.api_enabled { "api" } else { "scraper" },
-        previous_snapshot.map(|s| s.id),
-    )
-    .await?;
+    // A change pulls the previous snapshot's content back out of storage to
+    // diff against, so the diff itself (and the severity/confidence it
+    // drives) is computed before the new snapshot row is written. Alongside
+    // the section-level severity/confidence analysis, a block-level diff
+    // (see `analyzer_wasm::diff_blocks`) produces the human-readable
+    // change_summary that `get_change` surfaces.
+    let previous_text = if change_detected {
+        match &previous_snapshot {
+            Some(prev) => {
+                let previous_bytes = state.storage.get(&prev.storage_key).await?;
+                Some(String::from_utf8_lossy(&previous_bytes).into_owned())
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+    let analysis = previous_text
+        .as_deref()
+        .map(|previous_text| diff::analyze_change(previous_text, content));
+    let block_diff = previous_text
+        .as_deref()
+        .map(|previous_text| analyzer_wasm::diff_blocks(previous_text, content));
+    let diff_summary = analysis
+        .as_ref()
+        .map(|a| serde_json::to_value(&a.affected_sections))
+        .transpose()?;
 
-    // If change detected, create policy change record
-    if change_detected {
-        info!("Change detected in {} for {}", document_type, platform.name);
+    // Write the full content through the configured storage backend, keyed
+    // by checksum so identical snapshots dedupe - only the reference and
+    // checksum are persisted in the database.
+    let storage_ref = state.storage.put(&checksum, content.as_bytes()).await?;
 
-        db::create_policy_change(
-            &state.db,
+    // Create new snapshot
+    let snapshot = state
+        .db
+        .create_policy_snapshot(
             doc.id,
-            previous_snapshot.map(|s| s.id),
-            Some(snapshot.id),
-            "modification",
-            Some(&format!("Content changed from checksum {} to {}",
-                previous_checksum.as_deref().unwrap_or("none"),
-                checksum
-            )),
+            &storage_ref,
+            word_count,
+            char_count,
+            &checksum,
+            capture_method,
+            previous_snapshot_id,
+            diff_summary,
         )
         .await?;
+
+    // If change detected, create policy change record
+    if let Some(analysis) = analysis {
+        info!(
+            "Change detected in {} for {} (severity={}, confidence={:.2})",
+            document_type,
+            platform.name,
+            analysis.severity.as_str(),
+            analysis.confidence_score
+        );
+
+        let requires_member_notification = analysis.severity >= diff::NOTIFY_THRESHOLD;
+        let change_summary = block_diff
+            .as_ref()
+            .map(summarize_block_diff)
+            .unwrap_or_else(|| {
+                format!(
+                    "Content changed from checksum {} to {}",
+                    previous_checksum.as_deref().unwrap_or("none"),
+                    checksum
+                )
+            });
+        let change = state
+            .db
+            .create_policy_change(
+                doc.id,
+                previous_snapshot_id,
+                Some(snapshot.id),
+                "modification",
+                Some(&change_summary),
+                analysis.severity.as_str(),
+                analysis.confidence_score,
+                serde_json::to_value(&analysis.affected_sections)?,
+                requires_member_notification,
+            )
+            .await?;
+
+        let event = PolicyChangeEvent {
+            platform_id: platform.id,
+            platform: platform.name.clone(),
+            document_type: document_type.to_string(),
+            url: url.to_string(),
+            old_checksum: previous_checksum.clone(),
+            new_checksum: checksum.clone(),
+            snapshot_id: snapshot.id,
+            severity: change.severity,
+        };
+        if let Err(e) = events::publish_change_event(state, &event).await {
+            warn!("Failed to publish change event for {}: {}", platform.name, e);
+        }
     } else {
         info!("No change detected in {} for {}", document_type, platform.name);
     }
@@ -113,13 +367,43 @@ async fn collect_document(
         platform_id: platform.id,
         document_id: doc.id,
         snapshot_id: snapshot.id,
-        content,
+        content: content.to_string(),
         checksum,
         change_detected,
         previous_checksum,
     })
 }
 
+/// Turns a block-level [`BlockDiff`] into the human-readable sentence
+/// `get_change` surfaces as `change_summary`, e.g. "2 passage(s) added, 1
+/// passage(s) removed (e.g. added \"<first added passage>\")".
+fn summarize_block_diff(diff: &BlockDiff) -> String {
+    let mut parts = Vec::new();
+    if !diff.added.is_empty() {
+        parts.push(format!("{} passage(s) added", diff.added.len()));
+    }
+    if !diff.removed.is_empty() {
+        parts.push(format!("{} passage(s) removed", diff.removed.len()));
+    }
+    if !diff.modified.is_empty() {
+        parts.push(format!("{} passage(s) reworded", diff.modified.len()));
+    }
+
+    if parts.is_empty() {
+        return "No substantive wording changes detected".to_string();
+    }
+
+    let preview = diff
+        .modified
+        .first()
+        .map(|m| format!(" (e.g. \"{}\" -> \"{}\")", m.old, m.new))
+        .or_else(|| diff.added.first().map(|a| format!(" (e.g. added \"{a}\")")))
+        .or_else(|| diff.removed.first().map(|r| format!(" (e.g. removed \"{r}\")")))
+        .unwrap_or_default();
+
+    format!("{}{}", parts.join(", "), preview)
+}
+
 fn extract_urls(json_value: &serde_json::Value) -> Result<Vec<String>> {
     match json_value.as_array() {
         Some(arr) => Ok(arr
@@ -0,0 +1,53 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A durable unit of work persisted in the `job_queue` table. Replaces the
+/// old in-memory `CollectionJob`/`JobStatus` pair, which was discarded the
+/// moment a `tokio::spawn`ed task finished - or crashed.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QueuedJob {
+    pub id: Uuid,
+    pub queue: String,
+    pub job: serde_json::Value,
+    pub status: JobQueueStatus,
+    pub retries: i32,
+    pub heartbeat: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "job_status", rename_all = "lowercase")]
+pub enum JobQueueStatus {
+    New,
+    Running,
+}
+
+/// Payload enqueued for the `"collection"` queue: which platform to run a
+/// collection cycle for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionJobPayload {
+    pub platform_id: Uuid,
+    pub platform_name: String,
+}
+
+/// A poison message: a claimed job's `job` column didn't deserialize into
+/// the payload type the worker expected. Reported rather than retried
+/// forever, so one bad row can't wedge the queue.
+#[derive(Debug)]
+pub struct InvalidJob {
+    pub job_id: Uuid,
+    pub reason: String,
+}
+
+impl fmt::Display for InvalidJob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "job {} has an invalid payload: {}", self.job_id, self.reason)
+    }
+}
+
+impl std::error::Error for InvalidJob {}
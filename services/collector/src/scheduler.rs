@@ -1,76 +1,236 @@
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
 use tokio_cron_scheduler::{Job, JobScheduler};
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+use crate::{
+    maintenance,
+    platforms,
+    queue::{CollectionJobPayload, InvalidJob},
+    AppState,
+};
 
-use crate::{db, platforms, AppState};
+const COLLECTION_QUEUE: &str = "collection";
 
 pub async fn start_scheduler(state: AppState) -> Result<JobScheduler> {
     let scheduler = JobScheduler::new().await?;
 
     // Schedule platform collection jobs
+    let maintenance_state = state.clone();
     let collection_job = Job::new_async("0 */15 * * * *", move |_uuid, _l| {
         let state_clone = state.clone();
         Box::pin(async move {
-            if let Err(e) = run_collection_cycle(&state_clone).await {
-                error!("Collection cycle failed: {}", e);
+            if let Err(e) = enqueue_collection_cycle(&state_clone).await {
+                error!("Failed to enqueue collection cycle: {}", e);
             }
         })
     })?;
 
     scheduler.add(collection_job).await?;
 
-    // Start the scheduler
+    // The retention/vacuum/verification sweep is deliberately low-frequency
+    // and opt-in - unlike collection, nothing breaks if it's off, so it
+    // only runs when an operator has explicitly enabled it.
+    if maintenance_state.config.maintenance.scheduled_enabled {
+        let schedule_cron = maintenance_state.config.maintenance.schedule_cron.clone();
+        let maintenance_job = Job::new_async(schedule_cron.as_str(), move |_uuid, _l| {
+            let state_clone = maintenance_state.clone();
+            Box::pin(async move {
+                run_scheduled_maintenance(&state_clone).await;
+            })
+        })?;
+        scheduler.add(maintenance_job).await?;
+        info!(
+            "Scheduled maintenance job registered ({})",
+            maintenance_state.config.maintenance.schedule_cron
+        );
+    }
+
     scheduler.start().await?;
 
-    info!("Scheduler started - will run collection every 15 minutes");
+    info!("Scheduler started - will enqueue collection jobs every 15 minutes");
 
     Ok(scheduler)
 }
 
-async fn run_collection_cycle(state: &AppState) -> Result<()> {
-    info!("Starting scheduled collection cycle");
+async fn run_scheduled_maintenance(state: &AppState) {
+    let retention_days = state.config.maintenance.retention_days;
 
-    let platforms = db::get_active_platforms(&state.db).await?;
-    info!("Found {} active platforms to collect", platforms.len());
+    if let Err(e) = maintenance::run_retention(state, retention_days).await {
+        error!("Scheduled retention run failed: {}", e);
+    }
+    if let Err(e) = maintenance::run_vacuum(state).await {
+        error!("Scheduled vacuum run failed: {}", e);
+    }
+    if let Err(e) = maintenance::run_verification(state).await {
+        error!("Scheduled verification run failed: {}", e);
+    }
+}
 
-    let mut handles = Vec::new();
+/// Starts the durable worker loop and the stale-job reaper as background
+/// tasks. Unlike the old `tokio::spawn` + discarded `JoinHandle` approach,
+/// work that's in flight when the process crashes isn't lost: it sits in
+/// `job_queue` as `running` until its heartbeat goes stale, at which point
+/// the reaper puts it back up for grabs.
+pub fn start_worker(state: AppState) {
+    let worker_state = state.clone();
+    tokio::spawn(async move {
+        run_worker_loop(worker_state).await;
+    });
+
+    tokio::spawn(async move {
+        run_reaper_loop(state).await;
+    });
+}
+
+/// Enqueues one `collection` job per active platform. Replaces the old
+/// in-place `tokio::spawn` fan-out: the worker loop (run separately, see
+/// `start_worker`) is what actually performs the collection.
+async fn enqueue_collection_cycle(state: &AppState) -> Result<()> {
+    info!("Enqueuing scheduled collection cycle");
+
+    let platforms = state.db.get_active_platforms().await?;
+    info!("Found {} active platforms to collect", platforms.len());
 
     for platform in platforms {
-        let state_clone = state.clone();
-        let platform_clone = platform.clone();
-
-        let handle = tokio::spawn(async move {
-            match platforms::collect_platform_policies(&state_clone, &platform_clone).await {
-                Ok(results) => {
-                    info!(
-                        "Collected {} documents for {}",
-                        results.len(),
-                        platform_clone.name
-                    );
-                    Ok(())
-                }
-                Err(e) => {
-                    error!("Failed to collect {}: {}", platform_clone.name, e);
-                    Err(e)
-                }
+        let payload = CollectionJobPayload {
+            platform_id: platform.id,
+            platform_name: platform.name.clone(),
+        };
+        state
+            .db
+            .enqueue_job(COLLECTION_QUEUE, serde_json::to_value(payload)?)
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn run_worker_loop(state: AppState) {
+    loop {
+        match state.db.claim_job(COLLECTION_QUEUE).await {
+            Ok(Some(job)) => {
+                let job_id = job.id;
+                let payload: CollectionJobPayload = match serde_json::from_value(job.job.clone()) {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        let invalid = InvalidJob {
+                            job_id,
+                            reason: e.to_string(),
+                        };
+                        error!("Dropping poison job: {}", invalid);
+                        if let Err(e) = state.db.complete_job(job_id).await {
+                            error!("Failed to drop poison job {}: {}", job_id, e);
+                        }
+                        continue;
+                    }
+                };
+
+                run_claimed_job(&state, job_id, payload).await;
+            }
+            Ok(None) => {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+            Err(e) => {
+                error!("Failed to poll collection queue: {}", e);
+                tokio::time::sleep(Duration::from_secs(5)).await;
             }
-        });
+        }
+    }
+}
 
-        handles.push(handle);
+async fn run_claimed_job(state: &AppState, job_id: uuid::Uuid, payload: CollectionJobPayload) {
+    let heartbeat_state = state.clone();
+    let heartbeat_interval = Duration::from_secs(state.config.collector.job_queue.heartbeat_interval_secs);
+    let heartbeat_handle = tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(heartbeat_interval).await;
+            if let Err(e) = heartbeat_state.db.heartbeat_job(job_id).await {
+                warn!("Failed to refresh heartbeat for job {}: {}", job_id, e);
+            }
+        }
+    });
+
+    let platform = match state.db.get_platform_by_id(payload.platform_id).await {
+        Ok(Some(platform)) => platform,
+        Ok(None) => {
+            warn!("Platform {} no longer exists, dropping job", payload.platform_id);
+            heartbeat_handle.abort();
+            let _ = state.db.complete_job(job_id).await;
+            return;
+        }
+        Err(e) => {
+            heartbeat_handle.abort();
+            fail_with_backoff(state, job_id, &payload.platform_name, e.into()).await;
+            return;
+        }
+    };
+
+    let started = Instant::now();
+    let result = platforms::collect_platform_policies(state, &platform).await;
+    heartbeat_handle.abort();
+
+    let elapsed = started.elapsed();
+    let slow_threshold = Duration::from_secs(state.config.collector.job_queue.slow_job_threshold_secs);
+    if elapsed > slow_threshold {
+        warn!(
+            "Collection job for {} took {:?}, exceeding the {:?} threshold",
+            platform.name, elapsed, slow_threshold
+        );
+    }
 
-        // Respect max concurrent collections
-        if handles.len() >= state.config.collector.max_concurrent_collections {
-            for handle in handles.drain(..) {
-                let _ = handle.await;
+    match result {
+        Ok(results) => {
+            info!(
+                "Collected {} documents for {}",
+                results.len(),
+                platform.name
+            );
+            if let Err(e) = state.db.complete_job(job_id).await {
+                error!("Failed to complete job {} for {}: {}", job_id, platform.name, e);
             }
         }
+        Err(e) => {
+            error!("Failed to collect {}: {}", platform.name, e);
+            fail_with_backoff(state, job_id, &platform.name, e).await;
+        }
     }
+}
 
-    // Wait for remaining jobs
-    for handle in handles {
-        let _ = handle.await;
+async fn fail_with_backoff(state: &AppState, job_id: uuid::Uuid, platform_name: &str, error: anyhow::Error) {
+    let job_queue_config = &state.config.collector.job_queue;
+
+    // Exponential backoff starting from a couple of seconds, capped well
+    // under the reap window so a job that keeps failing doesn't get
+    // mistaken for abandoned mid-backoff.
+    const BASE_BACKOFF_SECS: i64 = 2;
+    let backoff_cap_secs = 10i64.min(job_queue_config.stale_after_secs / 2).max(1);
+
+    if let Err(e) = state
+        .db
+        .fail_job(job_id, BASE_BACKOFF_SECS, backoff_cap_secs, job_queue_config.max_retries)
+        .await
+    {
+        error!(
+            "Failed to re-enqueue job {} for {} after error ({}): {}",
+            job_id, platform_name, error, e
+        );
     }
+}
 
-    info!("Collection cycle completed");
-    Ok(())
+async fn run_reaper_loop(state: AppState) {
+    let interval = Duration::from_secs(state.config.collector.job_queue.stale_after_secs.max(1) as u64);
+    loop {
+        tokio::time::sleep(interval).await;
+        match state
+            .db
+            .reap_stale_jobs(COLLECTION_QUEUE, state.config.collector.job_queue.stale_after_secs)
+            .await
+        {
+            Ok(0) => {}
+            Ok(reaped) => warn!("Reaped {} stale collection job(s)", reaped),
+            Err(e) => error!("Failed to reap stale collection jobs: {}", e),
+        }
+    }
 }
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use base64::Engine;
+use chrono::Utc;
+use openssl::hash::MessageDigest;
+use openssl::pkey::{PKey, Private};
+use openssl::rsa::Rsa;
+use openssl::sign::Signer;
+
+use crate::config::FediverseCredentials;
+
+/// An RSA keypair used to sign outbound HTTP requests with HTTP Signatures
+/// (draft-cavage-http-signatures), the standard auth mechanism Fediverse
+/// instances expect for authenticated ActivityPub fetches - mirrors the way
+/// Plume generates and holds a signing key per actor.
+#[derive(Clone)]
+pub struct SigningKey {
+    pub key_id: String,
+    private_key: PKey<Private>,
+}
+
+impl SigningKey {
+    pub fn from_credentials(credentials: &FediverseCredentials) -> Result<Self> {
+        let rsa = Rsa::private_key_from_pem(credentials.private_key_pem.as_bytes())?;
+        Ok(Self {
+            key_id: credentials.key_id.clone(),
+            private_key: PKey::from_rsa(rsa)?,
+        })
+    }
+
+    /// Generates a fresh ephemeral key when no key is configured. Real
+    /// deployments should configure `FEDIVERSE_SIGNING_PRIVATE_KEY_PEM` so
+    /// the same key (and key id) is presented across restarts.
+    pub fn generate(key_id: String) -> Result<Self> {
+        let rsa = Rsa::generate(2048)?;
+        Ok(Self {
+            key_id,
+            private_key: PKey::from_rsa(rsa)?,
+        })
+    }
+
+    pub fn public_key_pem(&self) -> Result<String> {
+        Ok(String::from_utf8(self.private_key.public_key_to_pem()?)?)
+    }
+
+    /// Builds the `Signature` header value over `(request-target)`, `host`,
+    /// and `date` using RSA-SHA256.
+    pub fn sign_request(&self, method: &str, path: &str, host: &str, date: &str) -> Result<String> {
+        let signing_string = format!(
+            "(request-target): {} {}\nhost: {}\ndate: {}",
+            method.to_lowercase(),
+            path,
+            host,
+            date
+        );
+
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.private_key)?;
+        signer.update(signing_string.as_bytes())?;
+        let signature = base64::engine::general_purpose::STANDARD.encode(signer.sign_to_vec()?);
+
+        Ok(format!(
+            "keyId=\"{}\",algorithm=\"rsa-sha256\",headers=\"(request-target) host date\",signature=\"{}\"",
+            self.key_id, signature
+        ))
+    }
+}
+
+/// The `Date` header value (RFC 1123) that the signature is computed over.
+pub fn http_date_now() -> String {
+    Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
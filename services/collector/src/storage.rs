@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use crate::config::{S3StorageConfig, StorageConfig};
+
+/// Where a snapshot's raw content physically lives. Only this reference
+/// (plus the checksum that doubles as its key) is ever persisted in the
+/// database - the blob itself stays in whichever backend is configured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageRef {
+    pub backend: &'static str,
+    pub key: String,
+}
+
+/// A place to put and retrieve full policy snapshot text, addressed by
+/// content checksum so identical snapshots dedupe automatically.
+#[async_trait]
+pub trait SnapshotStorage: Send + Sync {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<StorageRef>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>>;
+}
+
+/// Stores snapshot blobs as files on the local filesystem.
+pub struct FilesystemStorage {
+    base_dir: PathBuf,
+}
+
+impl FilesystemStorage {
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+        }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.base_dir.join(key)
+    }
+}
+
+#[async_trait]
+impl SnapshotStorage for FilesystemStorage {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<StorageRef> {
+        tokio::fs::create_dir_all(&self.base_dir).await?;
+        let path = self.path_for(key);
+
+        // Identical content hashes to the same key, so a write that already
+        // happened (e.g. two platforms sharing boilerplate terms) is a no-op.
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            tokio::fs::write(&path, bytes).await?;
+        }
+
+        Ok(StorageRef {
+            backend: "local",
+            key: key.to_string(),
+        })
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        Ok(tokio::fs::read(self.path_for(key)).await?)
+    }
+}
+
+/// Stores snapshot blobs in an S3-compatible bucket.
+pub struct S3Storage {
+    bucket: s3::Bucket,
+}
+
+impl S3Storage {
+    pub fn new(config: &S3StorageConfig) -> Result<Self> {
+        let region = match &config.endpoint {
+            Some(endpoint) => s3::Region::Custom {
+                region: config.region.clone(),
+                endpoint: endpoint.clone(),
+            },
+            None => config.region.parse()?,
+        };
+
+        let credentials = s3::creds::Credentials::new(
+            Some(&config.access_key),
+            Some(&config.secret_key),
+            None,
+            None,
+            None,
+        )?;
+
+        Ok(Self {
+            bucket: s3::Bucket::new(&config.bucket, region, credentials)?,
+        })
+    }
+}
+
+#[async_trait]
+impl SnapshotStorage for S3Storage {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<StorageRef> {
+        if self.bucket.head_object(key).await.is_ok() {
+            return Ok(StorageRef {
+                backend: "s3",
+                key: key.to_string(),
+            });
+        }
+
+        self.bucket.put_object(key, bytes).await?;
+
+        Ok(StorageRef {
+            backend: "s3",
+            key: key.to_string(),
+        })
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self.bucket.get_object(key).await?;
+        Ok(response.bytes().to_vec())
+    }
+}
+
+/// Builds the [`SnapshotStorage`] backend selected by `config.storage`.
+pub fn build_storage(config: &StorageConfig) -> Result<Arc<dyn SnapshotStorage>> {
+    match config.backend.as_str() {
+        "s3" => {
+            let s3_config = config
+                .s3
+                .as_ref()
+                .ok_or_else(|| anyhow!("storage.backend = \"s3\" requires a storage.s3 section"))?;
+            Ok(Arc::new(S3Storage::new(s3_config)?))
+        }
+        _ => Ok(Arc::new(FilesystemStorage::new(&config.local_path))),
+    }
+}